@@ -30,7 +30,7 @@ fn main() {
   });
 
   scheduler.interruptible_with(move |t| {
-    if let Ok(_) = rx.try_recv() {
+    if rx.try_recv().is_ok() {
       println!("scheduler interrupted at {}", t);
       Interrupt::Break
     } else {
@@ -1,31 +1,39 @@
-use awoo::scheduler::RandomAccessScheduler;
 use awoo::time::simple::SimpleF32TimeGenerator;
-use awoo::window::Window;
+use awoo::timeline::{ActionRegistry, Timeline};
 use serde_json::from_str;
-use std::collections::HashMap;
 
-const WINDOWS: &str = r#"
+const TIMELINE: &str = r#"
 {
-  "a": {
-    "start": 0,
-    "end":   3
-  },
-  "b": {
-    "start": 3,
-    "end":  10
-  }
+  "windows": [
+    {
+      "name":  "a",
+      "start": 0,
+      "end":   3
+    },
+    {
+      "name":  "b",
+      "start": 3,
+      "end":  10,
+      "metadata": {
+        "comment": "main scene"
+      }
+    }
+  ]
 }"#;
 
 fn main() {
   let shared_resource = "Hello, world!".to_owned();
-  let windows: HashMap<String, Window<f32>> = from_str(WINDOWS).expect("cannot deserialize windows");
-  let a = windows.get("a").unwrap().map(|t| println!("{} in a: {}", shared_resource, t));
-  let b = windows.get("b").unwrap().map(|t| println!("{} in b: {}", shared_resource, t));
+  let timeline: Timeline<f32> = from_str(TIMELINE).expect("cannot deserialize timeline");
+
+  let mut registry = ActionRegistry::new();
+  registry
+    .register("a", |t| println!("{} in a: {}", shared_resource, t))
+    .register("b", |t| println!("{} in b: {}", shared_resource, t));
 
   let mut scheduler =
-    RandomAccessScheduler::new(
+    registry.random_access_scheduler(
       SimpleF32TimeGenerator::new(0., 1.),
-      vec![a, b]
+      &timeline
     ).expect("cannot create scheduler");
 
   scheduler.schedule();
@@ -0,0 +1,254 @@
+//! Easing functions.
+//!
+//! An easing function maps a normalized progress (in _[0; 1]_) to another progress, giving a
+//! feeling of acceleration or deceleration to animations. [`Easing`] provides the most common
+//! ones, along with cubic Bézier curves as found in CSS.
+//!
+//! Easing functions can be applied to the progress of a window (see [`LocalTime::eased`]) or to
+//! the segments of a keyframe track (see [`Interpolation::Eased`]).
+//!
+//! [`Easing`]: crate::easing::Easing
+//! [`LocalTime::eased`]: crate::window::LocalTime::eased
+//! [`Interpolation::Eased`]: crate::track::Interpolation::Eased
+
+#[cfg(feature = "serde")] use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Easing functions.
+///
+/// `In` variants accelerate from zero, `Out` variants decelerate to zero and `InOut` variants
+/// accelerate until the middle and then decelerate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Easing {
+  /// No easing.
+  #[default]
+  Linear,
+  /// Quadratic easing in.
+  QuadIn,
+  /// Quadratic easing out.
+  QuadOut,
+  /// Quadratic easing in and out.
+  QuadInOut,
+  /// Cubic easing in.
+  CubicIn,
+  /// Cubic easing out.
+  CubicOut,
+  /// Cubic easing in and out.
+  CubicInOut,
+  /// Quartic easing in.
+  QuartIn,
+  /// Quartic easing out.
+  QuartOut,
+  /// Quartic easing in and out.
+  QuartInOut,
+  /// Exponential easing in.
+  ExpoIn,
+  /// Exponential easing out.
+  ExpoOut,
+  /// Exponential easing in and out.
+  ExpoInOut,
+  /// Sinusoidal easing in.
+  SineIn,
+  /// Sinusoidal easing out.
+  SineOut,
+  /// Sinusoidal easing in and out.
+  SineInOut,
+  /// Elastic easing in (overshoots below zero).
+  ElasticIn,
+  /// Elastic easing out (overshoots above one).
+  ElasticOut,
+  /// Elastic easing in and out.
+  ElasticInOut,
+  /// Bouncing easing in.
+  BounceIn,
+  /// Bouncing easing out.
+  BounceOut,
+  /// Bouncing easing in and out.
+  BounceInOut,
+  /// Back easing in (goes slightly backwards first).
+  BackIn,
+  /// Back easing out (goes slightly too far first).
+  BackOut,
+  /// Back easing in and out.
+  BackInOut,
+  /// Cubic Bézier curve, given by its two control points `(x1, y1)` and `(x2, y2)`, as in CSS’
+  /// `cubic-bezier(x1, y1, x2, y2)`.
+  ///
+  /// `x1` and `x2` must be in _[0; 1]_.
+  CubicBezier(f32, f32, f32, f32)
+}
+
+impl Easing {
+  /// Ease a normalized progress.
+  ///
+  /// `t` is expected to be in _[0; 1]_. All easing functions map `0` to `0` and `1` to `1`.
+  pub fn ease(self, t: f32) -> f32 {
+    match self {
+      Easing::Linear => t,
+
+      Easing::QuadIn => t * t,
+      Easing::QuadOut => out(t, |t| t * t),
+      Easing::QuadInOut => in_out(t, |t| t * t),
+
+      Easing::CubicIn => t * t * t,
+      Easing::CubicOut => out(t, |t| t * t * t),
+      Easing::CubicInOut => in_out(t, |t| t * t * t),
+
+      Easing::QuartIn => t * t * t * t,
+      Easing::QuartOut => out(t, |t| t * t * t * t),
+      Easing::QuartInOut => in_out(t, |t| t * t * t * t),
+
+      Easing::ExpoIn => expo_in(t),
+      Easing::ExpoOut => out(t, expo_in),
+      Easing::ExpoInOut => in_out(t, expo_in),
+
+      Easing::SineIn => 1. - (t * PI * 0.5).cos(),
+      Easing::SineOut => (t * PI * 0.5).sin(),
+      Easing::SineInOut => (1. - (t * PI).cos()) * 0.5,
+
+      Easing::ElasticIn => elastic_in(t),
+      Easing::ElasticOut => out(t, elastic_in),
+      Easing::ElasticInOut => in_out(t, elastic_in),
+
+      Easing::BounceIn => out(t, bounce_out),
+      Easing::BounceOut => bounce_out(t),
+      Easing::BounceInOut => in_out(t, |t| 1. - bounce_out(1. - t)),
+
+      Easing::BackIn => back_in(t),
+      Easing::BackOut => out(t, back_in),
+      Easing::BackInOut => in_out(t, back_in),
+
+      Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t)
+    }
+  }
+
+  /// Whether the easing function is non-decreasing over _[0; 1]_.
+  ///
+  /// Elastic and back easing functions overshoot, bouncing easing functions go back and forth, and
+  /// cubic Bézier curves with control points outside of _[0; 1]_ overshoot too.
+  pub fn is_monotonic(self) -> bool {
+    match self {
+      Easing::ElasticIn | Easing::ElasticOut | Easing::ElasticInOut => false,
+      Easing::BounceIn | Easing::BounceOut | Easing::BounceInOut => false,
+      Easing::BackIn | Easing::BackOut | Easing::BackInOut => false,
+      Easing::CubicBezier(_, y1, _, y2) => (0. ..= 1.).contains(&y1) && (0. ..= 1.).contains(&y2),
+      _ => true
+    }
+  }
+}
+
+// turn an easing in into an easing out
+fn out<F>(t: f32, ease_in: F) -> f32 where F: Fn(f32) -> f32 {
+  1. - ease_in(1. - t)
+}
+
+// turn an easing in into an easing in and out
+fn in_out<F>(t: f32, ease_in: F) -> f32 where F: Fn(f32) -> f32 {
+  if t < 0.5 {
+    ease_in(t * 2.) * 0.5
+  } else {
+    1. - ease_in((1. - t) * 2.) * 0.5
+  }
+}
+
+fn expo_in(t: f32) -> f32 {
+  if t <= 0. {
+    0.
+  } else {
+    2f32.powf(10. * t - 10.)
+  }
+}
+
+fn elastic_in(t: f32) -> f32 {
+  if t <= 0. {
+    0.
+  } else if t >= 1. {
+    1.
+  } else {
+    -(2f32.powf(10. * t - 10.)) * ((t * 10. - 10.75) * 2. * PI / 3.).sin()
+  }
+}
+
+fn bounce_out(t: f32) -> f32 {
+  const N: f32 = 7.5625;
+  const D: f32 = 2.75;
+
+  if t < 1. / D {
+    N * t * t
+  } else if t < 2. / D {
+    let t = t - 1.5 / D;
+    N * t * t + 0.75
+  } else if t < 2.5 / D {
+    let t = t - 2.25 / D;
+    N * t * t + 0.9375
+  } else {
+    let t = t - 2.625 / D;
+    N * t * t + 0.984375
+  }
+}
+
+fn back_in(t: f32) -> f32 {
+  const C: f32 = 1.70158;
+  t * t * ((C + 1.) * t - C)
+}
+
+// one dimension of a cubic Bézier curve starting at 0 and ending at 1
+fn bezier(p1: f32, p2: f32, s: f32) -> f32 {
+  let r = 1. - s;
+  3. * r * r * s * p1 + 3. * r * s * s * p2 + s * s * s
+}
+
+fn bezier_derivative(p1: f32, p2: f32, s: f32) -> f32 {
+  let r = 1. - s;
+  3. * r * r * p1 + 6. * r * s * (p2 - p1) + 3. * s * s * (1. - p2)
+}
+
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+  if t <= 0. {
+    return 0.;
+  } else if t >= 1. {
+    return 1.;
+  }
+
+  // find the curve parameter s for which x(s) = t; try Newton’s method first, which converges
+  // quickly most of the time, and fall back to bisection otherwise
+  let mut s = t;
+
+  for _ in 0 .. 8 {
+    let x = bezier(x1, x2, s) - t;
+
+    if x.abs() < 1e-6 {
+      return bezier(y1, y2, s);
+    }
+
+    let dx = bezier_derivative(x1, x2, s);
+
+    if dx.abs() < 1e-6 {
+      break;
+    }
+
+    s -= x / dx;
+  }
+
+  let (mut lo, mut hi) = (0., 1.);
+  s = t;
+
+  for _ in 0 .. 32 {
+    let x = bezier(x1, x2, s);
+
+    if (x - t).abs() < 1e-6 {
+      break;
+    }
+
+    if x < t {
+      lo = s;
+    } else {
+      hi = s;
+    }
+
+    s = (lo + hi) * 0.5;
+  }
+
+  bezier(y1, y2, s)
+}
@@ -79,6 +79,11 @@
 //! [`Window<T>`]: crate::window::Window
 //! [`MappedWindow<_>`]: crate::window::MappedWindow
 
+pub mod easing;
+#[cfg(feature = "midi")] pub mod midi;
+#[cfg(feature = "rocket")] pub mod rocket;
 pub mod scheduler;
 pub mod time;
+#[cfg(feature = "serde")] pub mod timeline;
+pub mod track;
 pub mod window;
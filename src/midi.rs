@@ -0,0 +1,362 @@
+//! MIDI file import.
+//!
+//! A [`MidiImport`] reads a standard MIDI file and turns its note-on / note-off pairs into
+//! [`MidiNote`]s, holding time [`Window`]s in seconds, and its markers and cue points into
+//! [`MidiCue`]s. Tempo changes are taken into account when converting MIDI ticks to seconds.
+//!
+//! ```no_run
+//! use awoo::midi::MidiImport;
+//! use awoo::scheduler::layered::LayeredScheduler;
+//! use awoo::time::simple::SimpleTimeGenerator;
+//!
+//! let song = MidiImport::load("song.mid").unwrap();
+//!
+//! // flash on every kick drum (channel 10 is 9 when 0-based, key 36 is the bass drum)
+//! let windows = song
+//!   .windows(9, 36)
+//!   .into_iter()
+//!   .map(|window| window.map(|t| println!("kick: {}", t)))
+//!   .collect::<Vec<_>>();
+//!
+//! let mut scheduler = LayeredScheduler::new(SimpleTimeGenerator::new(0., 0.01), windows).unwrap();
+//! scheduler.schedule();
+//! ```
+//!
+//! With the `serde` feature, the import can also be converted to a [`Timeline`] document (see
+//! [`MidiImport::to_timeline`]).
+//!
+//! [`MidiImport`]: crate::midi::MidiImport
+//! [`MidiNote`]: crate::midi::MidiNote
+//! [`MidiCue`]: crate::midi::MidiCue
+//! [`Window`]: crate::window::Window
+//! [`Timeline`]: crate::timeline::Timeline
+//! [`MidiImport::to_timeline`]: crate::midi::MidiImport::to_timeline
+
+use midly::{Format, MetaMessage, MidiMessage, Smf, Timing, TrackEventKind};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[cfg(feature = "serde")] use crate::timeline::Timeline;
+use crate::window::Window;
+
+// tempo used until the first tempo change, in microseconds per beat (120 BPM)
+const DEFAULT_TEMPO: u32 = 500_000;
+
+/// A note played in a MIDI file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MidiNote {
+  /// Channel of the note, 0-based (_[0; 15]_).
+  pub channel: u8,
+  /// Key of the note (_[0; 127]_, 60 being the middle C).
+  pub key: u8,
+  /// Velocity of the note-on event (_[1; 127]_).
+  pub velocity: u8,
+  /// Time window, in seconds, during which the note is held.
+  pub window: Window<f64>
+}
+
+/// A marker or cue point of a MIDI file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiCue {
+  /// Time of the cue, in seconds.
+  pub t: f64,
+  /// Text of the marker or cue point.
+  pub name: String
+}
+
+/// Notes and cues imported from a MIDI file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MidiImport {
+  /// Notes, sorted by start time, then channel and key.
+  pub notes: Vec<MidiNote>,
+  /// Cues, sorted by time.
+  pub cues: Vec<MidiCue>,
+  /// Duration of the song, in seconds (time of the last event).
+  pub duration: f64
+}
+
+impl MidiImport {
+  /// Import a MIDI file from its content.
+  ///
+  /// Notes still held at the end of their track are released at the end of the track, and
+  /// zero-length notes are dropped. A note-on for a note already held on the same channel and key
+  /// releases it first, even if they come from different tracks.
+  pub fn parse(bytes: &[u8]) -> Result<Self, MidiError> {
+    let smf = Smf::parse(bytes)?;
+
+    // absolute ticks of the events of every track, along with the index of their track
+    let tracks: Vec<Vec<_>> = smf
+      .tracks
+      .iter()
+      .enumerate()
+      .map(|(i, track)| {
+        let mut tick = 0;
+        track
+          .iter()
+          .map(|event| {
+            tick += u64::from(event.delta.as_int());
+            (tick, i, event.kind)
+          })
+          .collect()
+      })
+      .collect();
+    let track_ends: Vec<_> = tracks.iter().map(|track| track.last().map_or(0, |&(tick, _, _)| tick)).collect();
+
+    let mut import = MidiImport::default();
+
+    if smf.header.format == Format::Sequential {
+      // tracks are independent songs played one after the other, each with its own tempo map
+      let mut offset = 0.;
+
+      for track in &tracks {
+        let tempo_map = TempoMap::new(smf.header.timing, tempo_changes(track));
+        let end = import.add_events(track, &track_ends, &tempo_map, offset);
+        offset = end;
+      }
+    } else {
+      // tracks are played together: merge them so that a note held on a channel and key is
+      // released by the next event for that note, whatever its track; tempo changes apply to all
+      // the tracks (they’re usually found in the first one)
+      let mut merged = tracks.concat();
+      merged.sort_by_key(|&(tick, _, _)| tick);
+
+      let tempo_map = TempoMap::new(smf.header.timing, tempo_changes(&merged));
+      import.add_events(&merged, &track_ends, &tempo_map, 0.);
+    }
+
+    import.notes.sort_by(|a, b| {
+      a.window
+        .start
+        .total_cmp(&b.window.start)
+        .then(a.channel.cmp(&b.channel))
+        .then(a.key.cmp(&b.key))
+    });
+    import.cues.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+    Ok(import)
+  }
+
+  /// Import a MIDI file.
+  pub fn load<P>(path: P) -> Result<Self, MidiError> where P: AsRef<Path> {
+    Self::parse(&fs::read(path)?)
+  }
+
+  /// Windows of the notes played on a given channel and key, sorted by start time.
+  ///
+  /// A given key never overlaps itself on a given channel, so those windows can be fed to any
+  /// scheduler.
+  pub fn windows(&self, channel: u8, key: u8) -> Vec<Window<f64>> {
+    self
+      .notes
+      .iter()
+      .filter(|note| note.channel == channel && note.key == key)
+      .map(|note| note.window)
+      .collect()
+  }
+
+  /// Convert the import to a [`Timeline`].
+  ///
+  /// Notes become windows named `note:<channel>:<key>` (channel being 0-based), on layer
+  /// `channel + 1`, with their velocity in the `velocity` metadata. Cues become windows named
+  /// `cue:<name>` on layer 0, lasting until the next cue (or the end of the song); cues with a
+  /// zero length are skipped. Such a timeline is meant to be run by a
+  /// [`LayeredScheduler`](crate::scheduler::layered::LayeredScheduler).
+  #[cfg(feature = "serde")]
+  pub fn to_timeline(&self) -> Timeline<f64> {
+    let mut timeline = Timeline::new();
+
+    for (i, cue) in self.cues.iter().enumerate() {
+      let end = self.cues.get(i + 1).map_or(self.duration, |next| next.t);
+
+      if cue.t < end {
+        timeline.push(format!("cue:{}", cue.name), Window::new(cue.t, end));
+      }
+    }
+
+    for note in &self.notes {
+      timeline
+        .push(format!("note:{}:{}", note.channel, note.key), note.window)
+        .with_layer(i32::from(note.channel) + 1)
+        .with_metadata("velocity", note.velocity.to_string());
+    }
+
+    timeline
+  }
+
+  // add the notes and cues of a stream of events sorted by tick, shifted by offset seconds; notes
+  // still held at the end are released at the end of their track; return the end time of the
+  // stream
+  fn add_events(
+    &mut self,
+    events: &[(u64, usize, TrackEventKind)],
+    track_ends: &[u64],
+    tempo_map: &TempoMap,
+    offset: f64
+  ) -> f64 {
+    let seconds = |tick| offset + tempo_map.seconds(tick);
+    let end_tick = events.last().map_or(0, |&(tick, _, _)| tick);
+
+    // held notes are released at the end of their track at the latest, even if the event releasing
+    // them comes later from another track
+    let release = |tick: u64, track: usize| seconds(tick.min(track_ends[track]));
+
+    // start tick, velocity and track of held notes, per channel and key
+    let mut held = HashMap::new();
+
+    for &(tick, track, kind) in events {
+      match kind {
+        TrackEventKind::Midi { channel, message } => {
+          let channel = channel.as_int();
+
+          match message {
+            MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+              let key = key.as_int();
+
+              let note = (tick, vel.as_int(), track);
+
+              if let Some((start, velocity, held_track)) = held.insert((channel, key), note) {
+                self.add_note(channel, key, velocity, seconds(start), release(tick, held_track));
+              }
+            }
+
+            MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+              let key = key.as_int();
+
+              if let Some((start, velocity, held_track)) = held.remove(&(channel, key)) {
+                self.add_note(channel, key, velocity, seconds(start), release(tick, held_track));
+              }
+            }
+
+            _ => ()
+          }
+        }
+
+        TrackEventKind::Meta(MetaMessage::Marker(text)) | TrackEventKind::Meta(MetaMessage::CuePoint(text)) => {
+          self.cues.push(MidiCue {
+            t: seconds(tick),
+            name: String::from_utf8_lossy(text).trim().to_owned()
+          });
+        }
+
+        _ => ()
+      }
+    }
+
+    for ((channel, key), (start, velocity, track)) in held {
+      self.add_note(channel, key, velocity, seconds(start), release(end_tick, track));
+    }
+
+    let end = seconds(end_tick);
+    self.duration = self.duration.max(end);
+    end
+  }
+
+  fn add_note(&mut self, channel: u8, key: u8, velocity: u8, start: f64, end: f64) {
+    if start < end {
+      self.notes.push(MidiNote {
+        channel,
+        key,
+        velocity,
+        window: Window::new(start, end)
+      });
+    }
+  }
+}
+
+// tempo changes of a stream of events, as (tick, microseconds per beat)
+fn tempo_changes<'a>(events: &'a [(u64, usize, TrackEventKind)]) -> impl Iterator<Item = (u64, u32)> + 'a {
+  events.iter().filter_map(|&(tick, _, kind)| match kind {
+    TrackEventKind::Meta(MetaMessage::Tempo(tempo)) => Some((tick, tempo.as_int())),
+    _ => None
+  })
+}
+
+// conversion of ticks to seconds
+struct TempoMap {
+  // seconds per tick, for files with timecode-based timing
+  timecode: Option<f64>,
+  ticks_per_beat: f64,
+  // tempo changes, as (tick, seconds at that tick, microseconds per beat), sorted by tick
+  changes: Vec<(u64, f64, u32)>
+}
+
+impl TempoMap {
+  fn new<I>(timing: Timing, tempos: I) -> Self where I: IntoIterator<Item = (u64, u32)> {
+    let (timecode, ticks_per_beat) = match timing {
+      Timing::Metrical(ticks_per_beat) => (None, f64::from(ticks_per_beat.as_int().max(1))),
+      Timing::Timecode(fps, subframes) => (Some(1. / (f64::from(fps.as_f32()) * f64::from(subframes.max(1)))), 1.)
+    };
+
+    let mut tempos: Vec<_> = tempos.into_iter().collect();
+    tempos.sort_by_key(|&(tick, _)| tick);
+
+    let mut changes = vec![(0, 0., DEFAULT_TEMPO)];
+
+    for (tick, tempo) in tempos {
+      let &(last_tick, last_seconds, last_tempo) = changes.last().unwrap();
+      let seconds = last_seconds + (tick - last_tick) as f64 * f64::from(last_tempo) * 1e-6 / ticks_per_beat;
+      changes.push((tick, seconds, tempo));
+    }
+
+    TempoMap {
+      timecode,
+      ticks_per_beat,
+      changes
+    }
+  }
+
+  fn seconds(&self, tick: u64) -> f64 {
+    if let Some(seconds_per_tick) = self.timecode {
+      return tick as f64 * seconds_per_tick;
+    }
+
+    // last change at or before tick; there’s always one at tick 0
+    let i = self.changes.partition_point(|&(change_tick, _, _)| change_tick <= tick) - 1;
+    let (change_tick, seconds, tempo) = self.changes[i];
+
+    seconds + (tick - change_tick) as f64 * f64::from(tempo) * 1e-6 / self.ticks_per_beat
+  }
+}
+
+/// Errors that might occur when importing MIDI files.
+#[derive(Debug)]
+pub enum MidiError {
+  /// The file couldn’t be read.
+  Io(io::Error),
+  /// The file is not a valid MIDI file.
+  Parse(midly::Error)
+}
+
+impl From<io::Error> for MidiError {
+  fn from(err: io::Error) -> Self {
+    MidiError::Io(err)
+  }
+}
+
+impl From<midly::Error> for MidiError {
+  fn from(err: midly::Error) -> Self {
+    MidiError::Parse(err)
+  }
+}
+
+impl fmt::Display for MidiError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      MidiError::Io(ref err) => write!(f, "cannot read MIDI file: {}", err),
+      MidiError::Parse(ref err) => write!(f, "invalid MIDI file: {}", err)
+    }
+  }
+}
+
+impl Error for MidiError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      MidiError::Io(ref err) => Some(err),
+      MidiError::Parse(ref err) => Some(err)
+    }
+  }
+}
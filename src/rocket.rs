@@ -0,0 +1,292 @@
+//! [GNU Rocket] sync-tracker support.
+//!
+//! GNU Rocket is a sync-tracker: values are edited as keys on rows of tracks in an editor, and the
+//! demo samples the tracks at the current row. A [`RocketTrack`] holds the keys of such a track. It
+//! can be read from and written to the `.track` files exported by Rocket, and converted to a
+//! [`Track`] keyed by rows:
+//!
+//! ```
+//! use awoo::rocket::{RocketInterpolation, RocketKey, RocketTrack};
+//!
+//! let mut track = RocketTrack::new("camera:fov");
+//! track.set_key(RocketKey::new(0, 60., RocketInterpolation::Linear));
+//! track.set_key(RocketKey::new(8, 90., RocketInterpolation::Step));
+//!
+//! assert_eq!(track.sample(4.), 75.);
+//! assert_eq!(track.sample(16.), 90.);
+//! ```
+//!
+//! While editing, the demo connects to the editor with a [`RocketClient`] (see the [`client`]
+//! module), which keeps tracks up to date and drives a time generator.
+//!
+//! [GNU Rocket]: https://github.com/rocket/rocket
+//! [`RocketTrack`]: crate::rocket::RocketTrack
+//! [`Track`]: crate::track::Track
+//! [`RocketClient`]: crate::rocket::client::RocketClient
+//! [`client`]: crate::rocket::client
+
+pub mod client;
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::easing::Easing;
+use crate::track::{Interpolation, Key, Track};
+
+/// Interpolation of a Rocket key, from that key to the next one.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RocketInterpolation {
+  /// Hold the value of the key until the next key.
+  Step,
+  /// Linear interpolation.
+  Linear,
+  /// Smoothstep interpolation (_3t² - 2t³_).
+  Smooth,
+  /// Quadratic interpolation (_t²_).
+  Ramp
+}
+
+impl RocketInterpolation {
+  /// Decode an interpolation as found in `.track` files and in the Rocket protocol.
+  pub fn from_u8(interpolation: u8) -> Result<Self, RocketError> {
+    match interpolation {
+      0 => Ok(RocketInterpolation::Step),
+      1 => Ok(RocketInterpolation::Linear),
+      2 => Ok(RocketInterpolation::Smooth),
+      3 => Ok(RocketInterpolation::Ramp),
+      _ => Err(RocketError::UnknownInterpolation(interpolation))
+    }
+  }
+
+  /// Encode an interpolation as found in `.track` files and in the Rocket protocol.
+  pub fn to_u8(self) -> u8 {
+    match self {
+      RocketInterpolation::Step => 0,
+      RocketInterpolation::Linear => 1,
+      RocketInterpolation::Smooth => 2,
+      RocketInterpolation::Ramp => 3
+    }
+  }
+
+  /// Equivalent track [`Interpolation`].
+  ///
+  /// Smoothstep is expressed as the cubic Bézier curve _(1/3, 0, 2/3, 1)_, which it’s exactly
+  /// equal to.
+  pub fn to_interpolation(self) -> Interpolation {
+    match self {
+      RocketInterpolation::Step => Interpolation::Step,
+      RocketInterpolation::Linear => Interpolation::Linear,
+      RocketInterpolation::Smooth => Interpolation::Eased(Easing::CubicBezier(1. / 3., 0., 2. / 3., 1.)),
+      RocketInterpolation::Ramp => Interpolation::Eased(Easing::QuadIn)
+    }
+  }
+}
+
+/// A key of a [`RocketTrack`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RocketKey {
+  /// Row of the key.
+  pub row: u32,
+  /// Value of the key.
+  pub value: f32,
+  /// Interpolation to use from that key to the next one.
+  pub interpolation: RocketInterpolation
+}
+
+impl RocketKey {
+  /// Create a new key.
+  pub fn new(row: u32, value: f32, interpolation: RocketInterpolation) -> Self {
+    RocketKey {
+      row,
+      value,
+      interpolation
+    }
+  }
+}
+
+/// A named Rocket track.
+///
+/// Keys are kept sorted by row, with at most one key per row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RocketTrack {
+  name: String,
+  keys: Vec<RocketKey>,
+  // keys converted to a track, rebuilt every time keys change
+  track: Track<f64, f32>
+}
+
+impl RocketTrack {
+  /// Create a new track without keys.
+  pub fn new<N>(name: N) -> Self where N: Into<String> {
+    RocketTrack {
+      name: name.into(),
+      keys: Vec::new(),
+      track: Track::new(Vec::new())
+    }
+  }
+
+  /// Name of the track.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Keys of the track, sorted by row.
+  pub fn keys(&self) -> &[RocketKey] {
+    &self.keys
+  }
+
+  /// Track of the keys, keyed by rows.
+  pub fn track(&self) -> &Track<f64, f32> {
+    &self.track
+  }
+
+  /// Set a key, replacing the key already on the same row, if any.
+  pub fn set_key(&mut self, key: RocketKey) {
+    match self.keys.binary_search_by_key(&key.row, |key| key.row) {
+      Ok(i) => self.keys[i] = key,
+      Err(i) => self.keys.insert(i, key)
+    }
+
+    self.rebuild();
+  }
+
+  /// Delete the key on a given row, if any, and return it.
+  pub fn delete_key(&mut self, row: u32) -> Option<RocketKey> {
+    let i = self.keys.binary_search_by_key(&row, |key| key.row).ok()?;
+    let key = self.keys.remove(i);
+
+    self.rebuild();
+    Some(key)
+  }
+
+  /// Sample the track at a given (fractional) row.
+  ///
+  /// A track without keys is always `0`.
+  pub fn sample(&self, row: f64) -> f32 {
+    self.track.sample(row).unwrap_or(0.)
+  }
+
+  /// Read a track in the `.track` format.
+  ///
+  /// The format is the one of Rocket: the number of keys as a 32-bit integer followed by, for each
+  /// key, its row as a 32-bit integer, its value as a 32-bit float and its interpolation as a byte,
+  /// all in little-endian.
+  pub fn read<N, R>(name: N, mut reader: R) -> Result<Self, RocketError> where N: Into<String>, R: Read {
+    let mut track = RocketTrack::new(name);
+    let len = read_u32_le(&mut reader)?;
+
+    for _ in 0 .. len {
+      let row = read_u32_le(&mut reader)?;
+      let value = f32::from_bits(read_u32_le(&mut reader)?);
+      let mut interpolation = [0];
+      reader.read_exact(&mut interpolation)?;
+
+      let key = RocketKey::new(row, value, RocketInterpolation::from_u8(interpolation[0])?);
+
+      match track.keys.binary_search_by_key(&row, |key| key.row) {
+        Ok(i) => track.keys[i] = key,
+        Err(i) => track.keys.insert(i, key)
+      }
+    }
+
+    track.rebuild();
+    Ok(track)
+  }
+
+  /// Write the track in the `.track` format (see [`RocketTrack::read`]).
+  pub fn write<W>(&self, mut writer: W) -> Result<(), io::Error> where W: Write {
+    writer.write_all(&(self.keys.len() as u32).to_le_bytes())?;
+
+    for key in &self.keys {
+      writer.write_all(&key.row.to_le_bytes())?;
+      writer.write_all(&key.value.to_bits().to_le_bytes())?;
+      writer.write_all(&[key.interpolation.to_u8()])?;
+    }
+
+    writer.flush()
+  }
+
+  /// Load a `.track` file.
+  pub fn load<N, P>(name: N, path: P) -> Result<Self, RocketError> where N: Into<String>, P: AsRef<Path> {
+    Self::read(name, BufReader::new(File::open(path)?))
+  }
+
+  /// Save the track to a `.track` file.
+  pub fn save<P>(&self, path: P) -> Result<(), io::Error> where P: AsRef<Path> {
+    self.write(BufWriter::new(File::create(path)?))
+  }
+
+  fn rebuild(&mut self) {
+    let keys: Vec<_> = self
+      .keys
+      .iter()
+      .map(|key| Key::new(f64::from(key.row), key.value, key.interpolation.to_interpolation()))
+      .collect();
+
+    self.track = Track::new(keys);
+  }
+}
+
+impl From<RocketTrack> for Track<f64, f32> {
+  fn from(track: RocketTrack) -> Self {
+    track.track
+  }
+}
+
+/// Path of the `.track` file of a track, as Rocket names it: `<prefix>_<name>.track`.
+pub fn track_path<P>(prefix: P, name: &str) -> PathBuf where P: AsRef<Path> {
+  let mut path = prefix.as_ref().as_os_str().to_owned();
+  path.push(format!("_{}.track", name));
+  path.into()
+}
+
+fn read_u32_le<R>(reader: &mut R) -> Result<u32, io::Error> where R: Read {
+  let mut bytes = [0; 4];
+  reader.read_exact(&mut bytes)?;
+  Ok(u32::from_le_bytes(bytes))
+}
+
+/// Errors that might occur with Rocket tracks and connections.
+#[derive(Debug)]
+pub enum RocketError {
+  /// A file or the connection couldn’t be read or written.
+  Io(io::Error),
+  /// Unknown key interpolation.
+  UnknownInterpolation(u8),
+  /// The editor didn’t answer the handshake properly.
+  Handshake,
+  /// The editor sent an unknown command.
+  UnknownCommand(u8),
+  /// The editor referred to a track that wasn’t requested.
+  UnknownTrack(u32)
+}
+
+impl From<io::Error> for RocketError {
+  fn from(err: io::Error) -> Self {
+    RocketError::Io(err)
+  }
+}
+
+impl fmt::Display for RocketError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      RocketError::Io(ref err) => write!(f, "Rocket I/O error: {}", err),
+      RocketError::UnknownInterpolation(interpolation) => write!(f, "unknown Rocket interpolation: {}", interpolation),
+      RocketError::Handshake => f.write_str("invalid Rocket handshake"),
+      RocketError::UnknownCommand(cmd) => write!(f, "unknown Rocket command: {}", cmd),
+      RocketError::UnknownTrack(track) => write!(f, "unknown Rocket track: {}", track)
+    }
+  }
+}
+
+impl Error for RocketError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      RocketError::Io(ref err) => Some(err),
+      _ => None
+    }
+  }
+}
@@ -0,0 +1,260 @@
+//! Rocket editor client.
+//!
+//! A [`RocketClient`] connects to a running Rocket editor over TCP. The tracks it requests are kept
+//! in sync with the editor, which also drives playback: moving the cursor in the editor seeks the
+//! time generator and pausing / resuming the editor pauses / resumes the demo. While playing, the
+//! current row is sent back to the editor so that its cursor follows the demo:
+//!
+//! ```no_run
+//! use awoo::rocket::client::RocketClient;
+//! use awoo::time::TimeGenerator;
+//! use awoo::time::simple::SimpleTimeGenerator;
+//!
+//! let mut client = RocketClient::connect("localhost:1338", "sync", 8.).unwrap();
+//! let mut gen = SimpleTimeGenerator::new(0., 1. / 60.);
+//! client.request_track("camera:fov").unwrap();
+//!
+//! loop {
+//!   client.update(&mut gen).unwrap();
+//!
+//!   let row = client.row(gen.current());
+//!   let _fov = client.track("camera:fov").unwrap().sample(row);
+//!   // render the frame
+//!
+//!   if !client.is_paused() {
+//!     gen.tick();
+//!   }
+//! }
+//! ```
+//!
+//! Times are in seconds, converted to rows with a fixed number of rows per second.
+//!
+//! [`RocketClient`]: crate::rocket::client::RocketClient
+
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+
+use crate::rocket::{track_path, RocketError, RocketInterpolation, RocketKey, RocketTrack};
+use crate::time::TimeGenerator;
+
+/// Default address of the Rocket editor.
+pub const DEFAULT_ADDR: &str = "localhost:1338";
+
+const CLIENT_GREETING: &[u8] = b"hello, synctracker!";
+const SERVER_GREETING: &[u8] = b"hello, demo!";
+
+const SET_KEY: u8 = 0;
+const DELETE_KEY: u8 = 1;
+const GET_TRACK: u8 = 2;
+const SET_ROW: u8 = 3;
+const PAUSE: u8 = 4;
+const SAVE_TRACKS: u8 = 5;
+
+/// A client connected to a Rocket editor.
+///
+/// Tracks are identified by the order in which they were requested, as in the Rocket protocol.
+#[derive(Debug)]
+pub struct RocketClient {
+  stream: TcpStream,
+  // bytes received but not parsed yet (commands are parsed only once complete)
+  pending: Vec<u8>,
+  tracks: Vec<RocketTrack>,
+  prefix: PathBuf,
+  rows_per_second: f64,
+  paused: bool,
+  // last row sent to or received from the editor
+  row: Option<u32>
+}
+
+impl RocketClient {
+  /// Connect to the editor at `addr` (usually [`DEFAULT_ADDR`]).
+  ///
+  /// When the editor asks to save tracks, they’re saved to `<prefix>_<name>.track` (see
+  /// [`track_path`]). The demo starts paused, as the editor does.
+  ///
+  /// [`track_path`]: crate::rocket::track_path
+  pub fn connect<A, P>(addr: A, prefix: P, rows_per_second: f64) -> Result<Self, RocketError>
+  where A: ToSocketAddrs,
+        P: Into<PathBuf> {
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_nodelay(true)?;
+    stream.write_all(CLIENT_GREETING)?;
+
+    let mut greeting = [0; SERVER_GREETING.len()];
+    stream.read_exact(&mut greeting).map_err(|_| RocketError::Handshake)?;
+
+    if greeting != SERVER_GREETING {
+      return Err(RocketError::Handshake);
+    }
+
+    Ok(RocketClient {
+      stream,
+      pending: Vec::new(),
+      tracks: Vec::new(),
+      prefix: prefix.into(),
+      rows_per_second,
+      paused: true,
+      row: None
+    })
+  }
+
+  /// Request a track from the editor, if not already requested.
+  ///
+  /// The track is empty until the editor sends its keys, which happens on the next calls to
+  /// [`RocketClient::update`].
+  pub fn request_track(&mut self, name: &str) -> Result<&RocketTrack, RocketError> {
+    let i = match self.tracks.iter().position(|track| track.name() == name) {
+      Some(i) => i,
+
+      None => {
+        let mut cmd = vec![GET_TRACK];
+        cmd.extend_from_slice(&(name.len() as u32).to_be_bytes());
+        cmd.extend_from_slice(name.as_bytes());
+        self.stream.write_all(&cmd)?;
+
+        self.tracks.push(RocketTrack::new(name));
+        self.tracks.len() - 1
+      }
+    };
+
+    Ok(&self.tracks[i])
+  }
+
+  /// Get a requested track by name.
+  pub fn track(&self, name: &str) -> Option<&RocketTrack> {
+    self.tracks.iter().find(|track| track.name() == name)
+  }
+
+  /// All the requested tracks, in request order.
+  pub fn tracks(&self) -> &[RocketTrack] {
+    &self.tracks
+  }
+
+  /// Whether the editor paused playback.
+  pub fn is_paused(&self) -> bool {
+    self.paused
+  }
+
+  /// Number of rows per second.
+  pub fn rows_per_second(&self) -> f64 {
+    self.rows_per_second
+  }
+
+  /// Convert a time, in seconds, to a (fractional) row.
+  pub fn row(&self, t: f64) -> f64 {
+    t * self.rows_per_second
+  }
+
+  /// Process the commands sent by the editor and synchronize `time_gen` with it.
+  ///
+  /// Keys edited in the editor are applied to the tracks, the time generator is set when the editor
+  /// changes the row and tracks are saved when the editor asks to. While playing, the current row
+  /// of `time_gen` is sent to the editor if it changed. This function doesn’t block.
+  pub fn update<G>(&mut self, time_gen: &mut G) -> Result<(), RocketError> where G: TimeGenerator<Time = f64> {
+    self.receive()?;
+
+    while let Some(len) = self.next_command_len()? {
+      let cmd: Vec<u8> = self.pending.drain(.. len).collect();
+      self.process(&cmd, time_gen)?;
+    }
+
+    if !self.paused {
+      let row = self.row(time_gen.current()).max(0.) as u32;
+
+      if self.row != Some(row) {
+        let mut cmd = vec![SET_ROW];
+        cmd.extend_from_slice(&row.to_be_bytes());
+        self.stream.write_all(&cmd)?;
+        self.row = Some(row);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Save all the requested tracks to `<prefix>_<name>.track`.
+  pub fn save_tracks(&self) -> Result<(), io::Error> {
+    for track in &self.tracks {
+      track.save(track_path(&self.prefix, track.name()))?;
+    }
+
+    Ok(())
+  }
+
+  // read everything available on the socket without blocking
+  fn receive(&mut self) -> Result<(), RocketError> {
+    self.stream.set_nonblocking(true)?;
+
+    let mut buf = [0; 4096];
+    let result = loop {
+      match self.stream.read(&mut buf) {
+        Ok(0) => break Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+        Ok(n) => self.pending.extend_from_slice(&buf[.. n]),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+        Err(ref err) if err.kind() == io::ErrorKind::Interrupted => (),
+        Err(err) => break Err(err.into())
+      }
+    };
+
+    self.stream.set_nonblocking(false)?;
+    result
+  }
+
+  // length of the first pending command, if it was completely received
+  fn next_command_len(&self) -> Result<Option<usize>, RocketError> {
+    let len = match self.pending.first() {
+      None => return Ok(None),
+      Some(&SET_KEY) => 1 + 4 + 4 + 4 + 1,
+      Some(&DELETE_KEY) => 1 + 4 + 4,
+      Some(&SET_ROW) => 1 + 4,
+      Some(&PAUSE) => 1 + 1,
+      Some(&SAVE_TRACKS) => 1,
+      Some(&cmd) => return Err(RocketError::UnknownCommand(cmd))
+    };
+
+    Ok(if self.pending.len() >= len { Some(len) } else { None })
+  }
+
+  fn process<G>(&mut self, cmd: &[u8], time_gen: &mut G) -> Result<(), RocketError> where G: TimeGenerator<Time = f64> {
+    match cmd[0] {
+      SET_KEY => {
+        let track = self.track_mut(be_u32(&cmd[1 ..]))?;
+        let key = RocketKey::new(
+          be_u32(&cmd[5 ..]),
+          f32::from_bits(be_u32(&cmd[9 ..])),
+          RocketInterpolation::from_u8(cmd[13])?
+        );
+
+        track.set_key(key);
+      }
+
+      DELETE_KEY => {
+        let track = self.track_mut(be_u32(&cmd[1 ..]))?;
+        track.delete_key(be_u32(&cmd[5 ..]));
+      }
+
+      SET_ROW => {
+        let row = be_u32(&cmd[1 ..]);
+        time_gen.set(f64::from(row) / self.rows_per_second);
+        self.row = Some(row);
+      }
+
+      PAUSE => self.paused = cmd[1] != 0,
+
+      SAVE_TRACKS => self.save_tracks()?,
+
+      cmd => return Err(RocketError::UnknownCommand(cmd))
+    }
+
+    Ok(())
+  }
+
+  fn track_mut(&mut self, index: u32) -> Result<&mut RocketTrack, RocketError> {
+    self.tracks.get_mut(index as usize).ok_or(RocketError::UnknownTrack(index))
+  }
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+  u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
@@ -3,18 +3,115 @@
 //! Schedulers are used to order and schedule [`MappedWindow`]s by using a [`TimeGenerator`]. You
 //! typically create a scheduler along with a time generator and a list ([`Vec`]) of time windows.
 //! Note that it’s possible that the creation of a scheduler fails because of the time windows. For
-//! instance, overlapping time windows are forbidden.
+//! instance, overlapping time windows are forbidden, unless you opt in for a [`LayeredScheduler`].
 //!
-//! Once the scheduler created, you can use it to schedule the mapped action in the windows.
+//! Once the scheduler created, you can use it to schedule the mapped action in the windows, either
+//! by letting it run the whole timeline (`schedule`) or by driving it one step at a time from your
+//! own main loop (`step`).
 //!
 //! [`MappedWindow`]: crate::window::MappedWindow
+//! [`LayeredScheduler`]: crate::scheduler::layered::LayeredScheduler
 //! [`TimeGenerator`]: crate::time::TimeGenerator
 
 use std::cmp::Ordering;
-use try_guard::guard;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::ops::Range;
 
-use crate::time::TimeGenerator;
-use crate::window::MappedWindow;
+use crate::time::{TimeGenerator, TimeOrd};
+use crate::window::{MappedWindow, Window};
+
+/// Generate the driving API shared by all schedulers.
+///
+/// The scheduler must be a struct with a `core` field (see [`Core`]).
+macro_rules! impl_scheduler {
+  ($scheduler:ident) => {
+    impl<'a, G> $scheduler<'a, G> where G: $crate::time::TimeGenerator {
+      /// Time generator of the scheduler.
+      pub fn time_gen(&self) -> &G {
+        &self.core.time_gen
+      }
+
+      /// Mutable time generator of the scheduler.
+      ///
+      /// That can be used to pause a time generator or change its delta while scheduling, for
+      /// instance.
+      pub fn time_gen_mut(&mut self) -> &mut G {
+        &mut self.core.time_gen
+      }
+
+      /// Replace the mapped windows, leaving the time generator untouched.
+      ///
+      /// The new windows are validated first; if they are invalid, the scheduler is left as-is.
+      /// Otherwise, the active windows, if any, are left, and the next evaluation enters the
+      /// windows active at that time. That is useful to reload a timeline during playback.
+      pub fn replace_windows<W>(
+        &mut self,
+        windows: W
+      ) -> Result<(), $crate::scheduler::SchedulerError<G::Time>>
+      where W: Into<Vec<$crate::window::MappedWindow<'a, G::Time>>> {
+        self.core.replace_windows(windows.into())
+      }
+
+      /// Reset the time generator to its initial value.
+      ///
+      /// Active windows, if any, are left.
+      pub fn reset(&mut self) {
+        self.core.reset();
+      }
+
+      /// Schedule the mapped windows.
+      ///
+      /// This resets the time generator and [`step`]s until the timeline is finished or the
+      /// scheduler gets interrupted.
+      ///
+      /// [`step`]: Self::step
+      pub fn schedule(&mut self) {
+        self.core.reset();
+        while let $crate::scheduler::StepResult::Continue = self.core.step() {}
+      }
+
+      /// Run a single step of the timeline.
+      ///
+      /// The actions of the windows active at the current time, if any, are performed, and then
+      /// time is ticked. This is useful if you already have a main loop (render loop, event pump,
+      /// etc.) and want to drive the scheduler from it.
+      pub fn step(&mut self) -> $crate::scheduler::StepResult {
+        self.core.step()
+      }
+
+      /// Set the time to `t` and run a single step of the timeline.
+      ///
+      /// See the documentation of [`step`] for further details.
+      ///
+      /// [`step`]: Self::step
+      pub fn step_at(&mut self, t: G::Time) -> $crate::scheduler::StepResult {
+        self.core.time_gen.set(t);
+        self.core.step()
+      }
+
+      /// Change the policy to apply to windows skipped over when time jumps.
+      ///
+      /// The default policy is [`SkipPolicy::Ignore`].
+      ///
+      /// [`SkipPolicy::Ignore`]: crate::scheduler::SkipPolicy::Ignore
+      pub fn set_skip_policy(&mut self, policy: $crate::scheduler::SkipPolicy) {
+        self.core.skip_policy = policy;
+      }
+
+      /// Make the scheduler interruptible with the given function
+      ///
+      /// > Note: the function must not block and return as soon as possible.
+      pub fn interruptible_with<F>(&mut self, interrupt: F)
+      where F: FnMut(G::Time) -> $crate::scheduler::Interrupt + 'a {
+        self.core.interrupt = Some(Box::new(interrupt));
+      }
+    }
+  }
+}
+
+pub mod layered;
 
 /// A random-access scheduler.
 ///
@@ -24,99 +121,486 @@ use crate::window::MappedWindow;
 /// unticking with a small delta.
 ///
 /// On the opposite hand, if you don’t need that random-access property, then a sequential
-/// scheduler will make a way better job for you (it will give you a _O(1)_ runtime performance
-/// instead of _O(log N)_).
+/// scheduler ([`SequentialScheduler`]) will make a way better job for you (it will give you a
+/// _O(1)_ runtime performance instead of _O(log N)_).
 ///
-/// > Note: if you use a sequential scheduler by doing random-accesses, you are basically ruining
+/// > Note: if you use a [`SequentialScheduler`] by doing random-accesses, you are basically ruining
 /// > the initial concept of a sequential scheduler (it will run in _O(N)_ at worst).
 pub struct RandomAccessScheduler<'a, G> where G: TimeGenerator {
-  time_gen: G,
-  windows: Vec<MappedWindow<'a, G::Time>>,
-  interrupt: Option<Box<FnMut(G::Time) -> Interrupt + 'a>>
+  core: Core<'a, G, BinarySearch>
 }
 
 impl<'a, G> RandomAccessScheduler<'a, G> where G: TimeGenerator {
   /// Create a new random-access scheduler.
   ///
-  /// This function might fail if the time windows are overlapping.
+  /// This function might fail if the time windows are overlapping or invalid.
   pub fn new<W>(
     time_gen: G,
     windows: W
-  ) -> Option<Self>
+  ) -> Result<Self, SchedulerError<G::Time>>
   where W: Into<Vec<MappedWindow<'a, G::Time>>> {
-    let mut windows = windows.into();
+    Ok(RandomAccessScheduler {
+      core: Core::new(time_gen, windows.into())?
+    })
+  }
 
-    windows.sort_by(|a, b| a.window.start.partial_cmp(&b.window.start).unwrap_or(Ordering::Less));
-    //
-    // ensure there’s no overlapping
-    let overlapping = windows.iter().zip(windows.iter().skip(1)).any(|(a, b)| {
-      b.window.start < a.window.end
-    });
-    guard!(!overlapping);
+  /// Seek to time `t`.
+  ///
+  /// The time generator is set to `t` and the timeline is evaluated at that time (see
+  /// [`evaluate_at`]). Time is not ticked afterwards.
+  ///
+  /// [`evaluate_at`]: Self::evaluate_at
+  pub fn seek(&mut self, t: G::Time) -> bool {
+    self.core.time_gen.set(t);
+    self.evaluate_at(t)
+  }
 
-    Some(RandomAccessScheduler {
-      time_gen,
-      windows,
-      interrupt: None
+  /// Evaluate the timeline at time `t`.
+  ///
+  /// The action of the window active at `t`, if any, is performed once for that time, leaving the
+  /// time generator untouched. Return whether a window was active.
+  ///
+  /// If the active window changed since the last evaluation, the previous window is left and the
+  /// new one is entered (see [`MappedWindow::on_enter`] and [`MappedWindow::on_leave`]).
+  ///
+  /// [`MappedWindow::on_enter`]: crate::window::MappedWindow::on_enter
+  /// [`MappedWindow::on_leave`]: crate::window::MappedWindow::on_leave
+  pub fn evaluate_at(&mut self, t: G::Time) -> bool {
+    self.core.evaluate_at(t) > 0
+  }
+}
+
+impl_scheduler!(RandomAccessScheduler);
+
+/// A sequential scheduler.
+///
+/// A sequential scheduler keeps a cursor on the last active window and moves it as time passes.
+/// When time is ticked forward (or backwards) with a small delta, finding the active window is
+/// then a _O(1)_ operation, which makes it the best choice for linear playback.
+///
+/// If time jumps (because of [`TimeGenerator::set`] for instance), the cursor falls back to a
+/// linear scan from its last position, which is _O(N)_ at worst. If you plan to jump around in
+/// time a lot, you should use a [`RandomAccessScheduler`] instead.
+///
+/// [`TimeGenerator::set`]: crate::time::TimeGenerator::set
+pub struct SequentialScheduler<'a, G> where G: TimeGenerator {
+  core: Core<'a, G, Cursor>
+}
+
+impl<'a, G> SequentialScheduler<'a, G> where G: TimeGenerator {
+  /// Create a new sequential scheduler.
+  ///
+  /// This function might fail if the time windows are overlapping or invalid.
+  pub fn new<W>(
+    time_gen: G,
+    windows: W
+  ) -> Result<Self, SchedulerError<G::Time>>
+  where W: Into<Vec<MappedWindow<'a, G::Time>>> {
+    Ok(SequentialScheduler {
+      core: Core::new(time_gen, windows.into())?
     })
   }
 
-  fn active_window_index(&self, t: G::Time) -> Option<usize> {
-    self.windows.binary_search_by(|win| {
-      match win.window.start.partial_cmp(&t).unwrap_or(Ordering::Less) {
+  /// Seek to time `t`.
+  ///
+  /// The time generator is set to `t` and the timeline is evaluated at that time (see
+  /// [`evaluate_at`]). Time is not ticked afterwards.
+  ///
+  /// [`evaluate_at`]: Self::evaluate_at
+  pub fn seek(&mut self, t: G::Time) -> bool {
+    self.core.time_gen.set(t);
+    self.evaluate_at(t)
+  }
+
+  /// Evaluate the timeline at time `t`.
+  ///
+  /// The action of the window active at `t`, if any, is performed once for that time, leaving the
+  /// time generator untouched. Return whether a window was active.
+  ///
+  /// If the active window changed since the last evaluation, the previous window is left and the
+  /// new one is entered (see [`MappedWindow::on_enter`] and [`MappedWindow::on_leave`]).
+  ///
+  /// [`MappedWindow::on_enter`]: crate::window::MappedWindow::on_enter
+  /// [`MappedWindow::on_leave`]: crate::window::MappedWindow::on_leave
+  pub fn evaluate_at(&mut self, t: G::Time) -> bool {
+    self.core.evaluate_at(t) > 0
+  }
+}
+
+impl_scheduler!(SequentialScheduler);
+
+/// Strategy to look up the windows active at a given time.
+trait Lookup<T>: Sized where T: TimeOrd {
+  /// Validate the windows and build the lookup over them, sorted by start time.
+  fn build<'a>(
+    windows: Vec<MappedWindow<'a, T>>
+  ) -> Result<(Self, Vec<MappedWindow<'a, T>>), SchedulerError<T>>;
+
+  /// Push the indices of the windows active at `t`, in the order their actions must run.
+  fn active_windows(&mut self, windows: &[MappedWindow<T>], t: T, active: &mut Vec<usize>);
+
+  /// Check whether the timeline is finished at time `t`.
+  fn is_finished(&self, windows: &[MappedWindow<T>], t: T) -> bool {
+    match windows.last() {
+      Some(last_win) => t.time_cmp(&last_win.window.end) != Ordering::Less,
+      None => true
+    }
+  }
+}
+
+/// Binary search over sorted, non-overlapping windows.
+struct BinarySearch;
+
+impl<T> Lookup<T> for BinarySearch where T: TimeOrd {
+  fn build<'a>(
+    windows: Vec<MappedWindow<'a, T>>
+  ) -> Result<(Self, Vec<MappedWindow<'a, T>>), SchedulerError<T>> {
+    Ok((BinarySearch, sort_windows(windows)?))
+  }
+
+  fn active_windows(&mut self, windows: &[MappedWindow<T>], t: T, active: &mut Vec<usize>) {
+    let found = windows.binary_search_by(|win| {
+      match win.window.start.time_cmp(&t) {
         Ordering::Equal => Ordering::Equal,
         Ordering::Greater => Ordering::Greater,
 
-        Ordering::Less => match t.partial_cmp(&win.window.end).unwrap_or(Ordering::Less) {
-          Ordering::Less | Ordering::Equal => Ordering::Equal,
-          Ordering::Greater => Ordering::Less
+        Ordering::Less => match t.time_cmp(&win.window.end) {
+          Ordering::Less => Ordering::Equal,
+          Ordering::Equal | Ordering::Greater => Ordering::Less
         }
       }
-    }).ok()
+    });
+
+    active.extend(found.ok());
+  }
+}
+
+/// Cursor on the last active window, over sorted, non-overlapping windows.
+struct Cursor {
+  cursor: usize
+}
+
+impl<T> Lookup<T> for Cursor where T: TimeOrd {
+  fn build<'a>(
+    windows: Vec<MappedWindow<'a, T>>
+  ) -> Result<(Self, Vec<MappedWindow<'a, T>>), SchedulerError<T>> {
+    Ok((Cursor { cursor: 0 }, sort_windows(windows)?))
+  }
+
+  fn active_windows(&mut self, windows: &[MappedWindow<T>], t: T, active: &mut Vec<usize>) {
+    let last = match windows.len().checked_sub(1) {
+      Some(last) => last,
+      None => return
+    };
+    let mut i = self.cursor.min(last);
+
+    if t.time_cmp(&windows[i].window.start) == Ordering::Less {
+      // time went backwards; scan back to the first window starting before t
+      while i > 0 && t.time_cmp(&windows[i].window.start) == Ordering::Less {
+        i -= 1;
+      }
+    } else {
+      // time went forward; scan up to the first window ending after t
+      while i < last && windows[i].window.end.time_cmp(&t) != Ordering::Greater {
+        i += 1;
+      }
+    }
+
+    self.cursor = i;
+
+    let win = &windows[i].window;
+    if win.start.time_cmp(&t) != Ordering::Greater && t.time_cmp(&win.end) == Ordering::Less {
+      active.push(i);
+    }
   }
+}
 
-  /// Schedule the mapped windows.
-  pub fn schedule(&mut self) {
+/// State and driving logic shared by all schedulers, parameterized by a window lookup strategy.
+struct Core<'a, G, L> where G: TimeGenerator {
+  time_gen: G,
+  // sorted by start time
+  windows: Vec<MappedWindow<'a, G::Time>>,
+  lookup: L,
+  interrupt: Option<Box<dyn FnMut(G::Time) -> Interrupt + 'a>>,
+  // windows active during the last evaluation
+  active: Vec<usize>,
+  // scratch buffer for the windows active during the current evaluation
+  next_active: Vec<usize>,
+  // time of the last evaluation, if any
+  last_time: Option<G::Time>,
+  skip_policy: SkipPolicy
+}
+
+impl<'a, G, L> Core<'a, G, L> where G: TimeGenerator, L: Lookup<G::Time> {
+  fn new(time_gen: G, windows: Vec<MappedWindow<'a, G::Time>>) -> Result<Self, SchedulerError<G::Time>> {
+    let (lookup, windows) = L::build(windows)?;
+
+    Ok(Core {
+      time_gen,
+      windows,
+      lookup,
+      interrupt: None,
+      active: Vec::new(),
+      next_active: Vec::new(),
+      last_time: None,
+      skip_policy: SkipPolicy::Ignore
+    })
+  }
+
+  fn replace_windows(&mut self, windows: Vec<MappedWindow<'a, G::Time>>) -> Result<(), SchedulerError<G::Time>> {
+    let (lookup, windows) = L::build(windows)?;
+
+    self.leave_all(self.time_gen.current());
+    self.lookup = lookup;
+    self.windows = windows;
+
+    Ok(())
+  }
+
+  fn reset(&mut self) {
+    self.leave_all(self.time_gen.current());
     self.time_gen.reset();
-    let mut t = self.time_gen.current();
+  }
 
-    loop {
-      if let Some(ref mut interrupt) = self.interrupt {
-        if let Interrupt::Break = (interrupt)(t) {
-          break;
-        }
+  fn step(&mut self) -> StepResult {
+    let t = self.time_gen.current();
+
+    if let Some(ref mut interrupt) = self.interrupt {
+      if let Interrupt::Break = (interrupt)(t) {
+        return StepResult::Interrupted;
       }
+    }
+
+    self.evaluate_at(t);
+    self.time_gen.tick();
+
+    let t = self.time_gen.current();
+    if self.lookup.is_finished(&self.windows, t) {
+      // the last tick might have jumped over windows, which hooks still need to run
+      self.transit(t, &[]);
+      self.active.clear();
+      self.last_time = None;
+      StepResult::Finished
+    } else {
+      StepResult::Continue
+    }
+  }
+
+  /// Evaluate the timeline at `t` and return the number of active windows.
+  fn evaluate_at(&mut self, t: G::Time) -> usize {
+    let mut next_active = mem::take(&mut self.next_active);
 
-      let win_ix = self.active_window_index(t);
+    next_active.clear();
+    self.lookup.active_windows(&self.windows, t, &mut next_active);
+    self.transit(t, &next_active);
 
-      if let Some(win_ix) = win_ix {
-        ((&mut self.windows[win_ix]).carry)(t);
+    for &win_ix in &next_active {
+      (self.windows[win_ix].carry)(t);
+    }
+
+    let count = next_active.len();
+    self.next_active = mem::replace(&mut self.active, next_active);
+    count
+  }
+
+  /// Move to time `t`, leaving windows not active anymore and entering windows that just became
+  /// active.
+  fn transit(&mut self, t: G::Time, next_active: &[usize]) {
+    for &win_ix in &self.active {
+      if !next_active.contains(&win_ix) {
+        self.windows[win_ix].leave(t);
       }
+    }
 
-      self.time_gen.tick();
-      t = self.time_gen.current();
+    // windows might be skipped even if the active windows didn’t change (from a gap to another one)
+    if let (SkipPolicy::Transit, Some(last_t)) = (self.skip_policy, self.last_time) {
+      match last_t.time_cmp(&t) {
+        Ordering::Less => {
+          let skipped = starting_in(&self.windows, last_t, t);
 
-      // check whether the simulation is done
-      if let Some(last_win) = self.windows.last() {
-        if t >= last_win.window.end {
-          break
+          for win in &mut self.windows[skipped] {
+            if win.window.end.time_cmp(&t) != Ordering::Greater {
+              win.enter(t);
+              win.leave(t);
+            }
+          }
         }
+
+        Ordering::Greater => {
+          let skipped = starting_in(&self.windows, t, last_t);
+
+          for win in self.windows[skipped].iter_mut().rev() {
+            if win.window.end.time_cmp(&last_t) != Ordering::Greater {
+              win.enter(t);
+              win.leave(t);
+            }
+          }
+        }
+
+        Ordering::Equal => ()
+      }
+    }
+
+    for &win_ix in next_active {
+      if !self.active.contains(&win_ix) {
+        self.windows[win_ix].enter(t);
       }
     }
+
+    self.last_time = Some(t);
   }
 
-  /// Make the scheduler interruptible with the given function
-  ///
-  /// > Note: the function must not block and return as soon as possible.
-  pub fn interruptible_with<F>(&mut self, interrupt: F) where F: FnMut(G::Time) -> Interrupt + 'a {
-    self.interrupt = Some(Box::new(interrupt));
+  /// Leave the active windows, if any, and forget about the last evaluation.
+  fn leave_all(&mut self, t: G::Time) {
+    for win_ix in self.active.drain(..) {
+      self.windows[win_ix].leave(t);
+    }
+
+    self.last_time = None;
   }
 }
 
+/// Range of windows, sorted by start time, starting in _(a; b]_.
+fn starting_in<T>(windows: &[MappedWindow<T>], a: T, b: T) -> Range<usize> where T: TimeOrd {
+  let lo = windows.partition_point(|win| win.window.start.time_cmp(&a) != Ordering::Greater);
+  let hi = windows.partition_point(|win| win.window.start.time_cmp(&b) != Ordering::Greater);
+
+  lo..hi
+}
+
+/// Validate mapped windows and sort them by start time.
+///
+/// Windows with incomparable bounds or with `start >= end` are rejected.
+pub(crate) fn validate_windows<T>(
+  windows: Vec<MappedWindow<T>>
+) -> Result<Vec<(usize, MappedWindow<T>)>, SchedulerError<T>>
+where T: TimeOrd {
+  for (index, win) in windows.iter().enumerate() {
+    let window = win.window;
+
+    match window.start.partial_cmp(&window.end) {
+      None => return Err(SchedulerError::IncomparableBounds { index, window }),
+      Some(Ordering::Equal) | Some(Ordering::Greater) => return Err(SchedulerError::EmptyWindow { index, window }),
+      Some(Ordering::Less) => ()
+    }
+  }
+
+  let mut windows: Vec<_> = windows.into_iter().enumerate().collect();
+  windows.sort_by(|(_, a), (_, b)| a.window.start.time_cmp(&b.window.start));
+
+  Ok(windows)
+}
+
+/// Validate mapped windows, sort them by start time and ensure none of them overlap.
+fn sort_windows<T>(windows: Vec<MappedWindow<T>>) -> Result<Vec<MappedWindow<T>>, SchedulerError<T>> where T: TimeOrd {
+  let windows = validate_windows(windows)?;
+
+  // ensure there’s no overlapping
+  let overlapping = windows.iter().zip(windows.iter().skip(1)).find(|((_, a), (_, b))| {
+    b.window.start.time_cmp(&a.window.end) == Ordering::Less
+  });
+
+  if let Some(((first, a), (second, b))) = overlapping {
+    return Err(SchedulerError::Overlapping {
+      first: *first,
+      first_window: a.window,
+      second: *second,
+      second_window: b.window
+    });
+  }
+
+  Ok(windows.into_iter().map(|(_, win)| win).collect())
+}
+
+/// Errors that might occur when creating a scheduler.
+///
+/// Window indices refer to the position of the windows in the list given to the scheduler.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SchedulerError<T> {
+  /// Two windows overlap.
+  Overlapping {
+    /// Index of the window starting first.
+    first: usize,
+    /// The window starting first.
+    first_window: Window<T>,
+    /// Index of the window starting second.
+    second: usize,
+    /// The window starting second.
+    second_window: Window<T>
+  },
+  /// A window is empty or inverted (i.e. `start >= end`).
+  EmptyWindow {
+    /// Index of the window.
+    index: usize,
+    /// The window.
+    window: Window<T>
+  },
+  /// A window has bounds that cannot be compared (e.g. `NaN`).
+  IncomparableBounds {
+    /// Index of the window.
+    index: usize,
+    /// The window.
+    window: Window<T>
+  }
+}
+
+impl<T> fmt::Display for SchedulerError<T> where T: fmt::Debug {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      SchedulerError::Overlapping { first, ref first_window, second, ref second_window } => {
+        write!(
+          f,
+          "window #{} [{:?}, {:?}) overlaps with window #{} [{:?}, {:?})",
+          first,
+          first_window.start,
+          first_window.end,
+          second,
+          second_window.start,
+          second_window.end
+        )
+      }
+
+      SchedulerError::EmptyWindow { index, ref window } => {
+        write!(f, "window #{} [{:?}, {:?}) is empty", index, window.start, window.end)
+      }
+
+      SchedulerError::IncomparableBounds { index, ref window } => {
+        write!(f, "window #{} [{:?}, {:?}) has incomparable bounds", index, window.start, window.end)
+      }
+    }
+  }
+}
+
+impl<T> Error for SchedulerError<T> where T: fmt::Debug {}
+
+/// Policy for windows skipped over when time jumps.
+///
+/// When time jumps (by seeking, or because of a large delta), some windows might lie entirely in
+/// between the previous time and the new one; those windows are never active, but they might have
+/// hooks to run when entered or left.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SkipPolicy {
+  /// Skipped windows are neither entered nor left.
+  Ignore,
+  /// Skipped windows are entered and left right away, in the order time went through them.
+  Transit
+}
+
+/// Result of a single scheduling step.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StepResult {
+  /// The timeline is not finished yet.
+  Continue,
+  /// The scheduler was interrupted before running the step.
+  Interrupted,
+  /// The timeline is finished.
+  Finished
+}
+
 /// Interruption mechanism.
 ///
 /// A scheduler has to check when an interruption has occurred. If one does, it must return from the
-/// `schedule` method and give back control-flow.
+/// `schedule` (or `step`) method and give back control-flow.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Interrupt {
   Break,
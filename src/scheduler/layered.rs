@@ -0,0 +1,152 @@
+//! Scheduling of overlapping windows.
+//!
+//! The schedulers from the parent module forbid overlapping windows, because a single action runs
+//! at any given time. Real timelines often need several actions at once, though: a camera track
+//! running across several scenes, a crossfade between two scenes, etc. A [`LayeredScheduler`]
+//! accepts overlapping windows and runs every active window, in a deterministic order:
+//!
+//!   1. By layer (see [`MappedWindow::with_layer`]), lower layers first.
+//!   2. By start time, earlier windows first.
+//!   3. By order of appearance in the list of windows given to the scheduler.
+//!
+//! [`MappedWindow::with_layer`]: crate::window::MappedWindow::with_layer
+
+use std::cmp::Ordering;
+
+use crate::scheduler::{validate_windows, Core, Lookup, SchedulerError};
+use crate::time::{TimeGenerator, TimeOrd};
+use crate::window::MappedWindow;
+
+/// A scheduler accepting overlapping windows.
+///
+/// Windows are stored in an interval tree, so that finding the windows active at a given time runs
+/// in _O(log N + K)_, _K_ being the number of active windows.
+pub struct LayeredScheduler<'a, G> where G: TimeGenerator {
+  core: Core<'a, G, IntervalTree<G::Time>>
+}
+
+impl<'a, G> LayeredScheduler<'a, G> where G: TimeGenerator {
+  /// Create a new layered scheduler.
+  ///
+  /// This function might fail if some time windows are invalid. Overlapping windows are accepted.
+  pub fn new<W>(
+    time_gen: G,
+    windows: W
+  ) -> Result<Self, SchedulerError<G::Time>>
+  where W: Into<Vec<MappedWindow<'a, G::Time>>> {
+    Ok(LayeredScheduler {
+      core: Core::new(time_gen, windows.into())?
+    })
+  }
+
+  /// Seek to time `t`.
+  ///
+  /// The time generator is set to `t` and the timeline is evaluated at that time (see
+  /// [`evaluate_at`]). Time is not ticked afterwards.
+  ///
+  /// [`evaluate_at`]: Self::evaluate_at
+  pub fn seek(&mut self, t: G::Time) -> usize {
+    self.core.time_gen.set(t);
+    self.evaluate_at(t)
+  }
+
+  /// Evaluate the timeline at time `t`.
+  ///
+  /// The actions of all the windows active at `t` are performed once for that time, leaving the
+  /// time generator untouched. Return the number of active windows.
+  ///
+  /// Windows that are not active anymore since the last evaluation are left, and windows that just
+  /// became active are entered.
+  pub fn evaluate_at(&mut self, t: G::Time) -> usize {
+    self.core.evaluate_at(t)
+  }
+}
+
+impl_scheduler!(LayeredScheduler);
+
+/// A static interval tree.
+///
+/// The tree is implicit: it’s laid out over windows sorted by start time, the root being the
+/// middle window and each subtree being one half of the remaining windows. Each node knows the
+/// greatest end time of its subtree, which allows to prune subtrees that cannot contain a given
+/// time.
+struct IntervalTree<T> {
+  max_end: Vec<T>
+}
+
+impl<T> Lookup<T> for IntervalTree<T> where T: TimeOrd {
+  fn build<'a>(
+    windows: Vec<MappedWindow<'a, T>>
+  ) -> Result<(Self, Vec<MappedWindow<'a, T>>), SchedulerError<T>> {
+    let windows: Vec<_> = validate_windows(windows)?.into_iter().map(|(_, win)| win).collect();
+    Ok((IntervalTree::new(&windows), windows))
+  }
+
+  fn active_windows(&mut self, windows: &[MappedWindow<T>], t: T, active: &mut Vec<usize>) {
+    self.query(windows, 0, windows.len(), t, active);
+
+    // windows are already sorted by start time (and stably so), so sorting by index breaks ties
+    active.sort_by_key(|&win_ix| (windows[win_ix].layer, win_ix));
+  }
+
+  fn is_finished(&self, _: &[MappedWindow<T>], t: T) -> bool {
+    if self.max_end.is_empty() {
+      true
+    } else {
+      t.time_cmp(&self.max_end[self.max_end.len() / 2]) != Ordering::Less
+    }
+  }
+}
+
+impl<T> IntervalTree<T> where T: TimeOrd {
+  fn new(windows: &[MappedWindow<T>]) -> Self {
+    let mut max_end: Vec<T> = windows.iter().map(|win| win.window.end).collect();
+    Self::build_max_end(&mut max_end, 0, windows.len());
+
+    IntervalTree { max_end }
+  }
+
+  // compute the greatest end time of the [lo, hi) subtree
+  fn build_max_end(max_end: &mut [T], lo: usize, hi: usize) -> Option<T> {
+    if lo >= hi {
+      return None;
+    }
+
+    let mid = lo + (hi - lo) / 2;
+    let mut end = max_end[mid];
+
+    for sub_end in Self::build_max_end(max_end, lo, mid).into_iter().chain(Self::build_max_end(max_end, mid + 1, hi)) {
+      if sub_end.time_cmp(&end) == Ordering::Greater {
+        end = sub_end;
+      }
+    }
+
+    max_end[mid] = end;
+    Some(end)
+  }
+
+  fn query(&self, windows: &[MappedWindow<T>], lo: usize, hi: usize, t: T, active: &mut Vec<usize>) {
+    if lo >= hi {
+      return;
+    }
+
+    let mid = lo + (hi - lo) / 2;
+
+    if t.time_cmp(&self.max_end[mid]) != Ordering::Less {
+      // no window in that subtree ends after t
+      return;
+    }
+
+    self.query(windows, lo, mid, t, active);
+
+    let win = &windows[mid].window;
+    if win.start.time_cmp(&t) != Ordering::Greater {
+      if t.time_cmp(&win.end) == Ordering::Less {
+        active.push(mid);
+      }
+
+      // windows on the right start after the current one, so they might still contain t
+      self.query(windows, mid + 1, hi, t, active);
+    }
+  }
+}
@@ -15,9 +15,252 @@
 //!     to get a time difference between two ticks. That difference is called a _delta_ and it’s
 //!     also possible to change it.
 //!
+//! Time itself must be _totally ordered_ so that schedulers can sort and look up time windows. See
+//! the [`TimeOrd`] trait for further details.
+//!
 //! [`TimeGenerator`]: crate::time::TimeGenerator
+//! [`TimeOrd`]: crate::time::TimeOrd
 
+pub mod adapter;
+pub mod drift_free;
+pub mod fixed_step;
+pub mod musical;
+pub mod rational;
+pub mod sample_clock;
 pub mod simple;
+pub mod timecode;
+pub mod wall_clock;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::ops::Sub;
+use std::time::Duration;
+
+/// Time types with a total order.
+///
+/// Most time types are already totally ordered ([`Ord`]), but floating-point numbers are not,
+/// because of `NaN`. Comparing time with [`PartialOrd`] would then give inconsistent orderings and
+/// bogus window lookups. That trait provides a total order for all time types; for floating-point
+/// numbers, it’s the usual order, `NaN` being ordered as with the IEEE 754 `totalOrder` predicate
+/// (positive `NaN` is greater than everything else). Unlike `totalOrder`, `-0` and `+0` are equal.
+pub trait TimeOrd: PartialOrd + Copy {
+  /// Compare two times.
+  fn time_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_TimeOrd_Ord {
+  ($($t:ty),*) => {
+    $(
+      impl TimeOrd for $t {
+        fn time_cmp(&self, other: &Self) -> Ordering {
+          self.cmp(other)
+        }
+      }
+    )*
+  }
+}
+
+impl_TimeOrd_Ord!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, Duration);
+
+macro_rules! impl_TimeOrd_float {
+  ($($t:ty),*) => {
+    $(
+      impl TimeOrd for $t {
+        fn time_cmp(&self, other: &Self) -> Ordering {
+          // fall back to the total order only for NaN, so that -0 and +0 stay equal
+          self.partial_cmp(other).unwrap_or_else(|| self.total_cmp(other))
+        }
+      }
+    )*
+  }
+}
+
+impl_TimeOrd_float!(f32, f64);
+
+/// Time types supporting basic arithmetic.
+///
+/// That trait is used by time generators that need to move time by a given delta. Unsigned time
+/// types (such as integers or [`Duration`]) saturate at zero instead of underflowing.
+pub trait Arithmetic: TimeOrd {
+  /// The zero time.
+  fn zero() -> Self;
+
+  /// Add two times.
+  fn add(self, rhs: Self) -> Self;
+
+  /// Subtract two times.
+  fn sub(self, rhs: Self) -> Self;
+
+  /// Multiply a time by a natural number.
+  fn times(self, n: u64) -> Self;
+
+  /// Remainder of the Euclidean division of a time by a positive time, in _[0; rhs)_.
+  fn rem(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_Arithmetic_float {
+  ($($t:ty),*) => {
+    $(
+      impl Arithmetic for $t {
+        fn zero() -> Self {
+          0.
+        }
+
+        fn add(self, rhs: Self) -> Self {
+          self + rhs
+        }
+
+        fn sub(self, rhs: Self) -> Self {
+          self - rhs
+        }
+
+        fn times(self, n: u64) -> Self {
+          self * n as $t
+        }
+
+        fn rem(self, rhs: Self) -> Self {
+          self.rem_euclid(rhs)
+        }
+      }
+    )*
+  }
+}
+
+impl_Arithmetic_float!(f32, f64);
+
+macro_rules! impl_Arithmetic_int {
+  ($($t:ty),*) => {
+    $(
+      impl Arithmetic for $t {
+        fn zero() -> Self {
+          0
+        }
+
+        fn add(self, rhs: Self) -> Self {
+          self.saturating_add(rhs)
+        }
+
+        fn sub(self, rhs: Self) -> Self {
+          self.saturating_sub(rhs)
+        }
+
+        fn times(self, n: u64) -> Self {
+          self.saturating_mul(<$t>::try_from(n).unwrap_or(<$t>::MAX))
+        }
+
+        fn rem(self, rhs: Self) -> Self {
+          self.rem_euclid(rhs)
+        }
+      }
+    )*
+  }
+}
+
+impl_Arithmetic_int!(u32, u64, i32, i64);
+
+impl Arithmetic for Duration {
+  fn zero() -> Self {
+    Duration::ZERO
+  }
+
+  fn add(self, rhs: Self) -> Self {
+    self.saturating_add(rhs)
+  }
+
+  fn sub(self, rhs: Self) -> Self {
+    self.saturating_sub(rhs)
+  }
+
+  fn times(self, n: u64) -> Self {
+    let nanos = self.as_nanos().saturating_mul(n as u128);
+    let secs = u64::try_from(nanos / 1_000_000_000).unwrap_or(u64::MAX);
+
+    Duration::new(secs, (nanos % 1_000_000_000) as u32)
+  }
+
+  fn rem(self, rhs: Self) -> Self {
+    let nanos = self.as_nanos() % rhs.as_nanos();
+    Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+  }
+}
+
+/// Time types that can be normalized.
+///
+/// Normalizing a time against a length gives the ratio of the former over the latter. It’s used to
+/// compute the progress of time inside a window, for instance.
+pub trait Normalize: Sub<Output = Self> + Copy {
+  /// Ratio of `self` over `length`.
+  fn normalize(self, length: Self) -> f32;
+}
+
+macro_rules! impl_Normalize_as {
+  ($($t:ty),*) => {
+    $(
+      impl Normalize for $t {
+        fn normalize(self, length: Self) -> f32 {
+          (self as f64 / length as f64) as f32
+        }
+      }
+    )*
+  }
+}
+
+impl_Normalize_as!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f64);
+
+impl Normalize for f32 {
+  fn normalize(self, length: Self) -> f32 {
+    self / length
+  }
+}
+
+impl Normalize for Duration {
+  fn normalize(self, length: Self) -> f32 {
+    (self.as_secs_f64() / length.as_secs_f64()) as f32
+  }
+}
+
+/// Time types that can be scaled by a real factor.
+///
+/// That trait is used to speed up or slow down time (see [`Scale`]). Integer time types are
+/// rounded to the closest integer and saturate at their bounds.
+///
+/// [`Scale`]: crate::time::adapter::Scale
+pub trait Scalable: Arithmetic {
+  /// Multiply a time by `factor`.
+  fn scale(self, factor: f64) -> Self;
+}
+
+impl Scalable for f32 {
+  fn scale(self, factor: f64) -> Self {
+    (f64::from(self) * factor) as f32
+  }
+}
+
+impl Scalable for f64 {
+  fn scale(self, factor: f64) -> Self {
+    self * factor
+  }
+}
+
+macro_rules! impl_Scalable_int {
+  ($($t:ty),*) => {
+    $(
+      impl Scalable for $t {
+        fn scale(self, factor: f64) -> Self {
+          (self as f64 * factor).round() as $t
+        }
+      }
+    )*
+  }
+}
+
+impl_Scalable_int!(u32, u64, i32, i64);
+
+impl Scalable for Duration {
+  fn scale(self, factor: f64) -> Self {
+    Duration::try_from_secs_f64(self.as_secs_f64() * factor.max(0.)).unwrap_or(Duration::MAX)
+  }
+}
 
 /// Set of types that can handle time.
 ///
@@ -30,7 +273,7 @@ pub mod simple;
 ///   - Change the internal delta time used to tick / untick.
 pub trait TimeGenerator {
   /// Type of time.
-  type Time: PartialOrd + Copy;
+  type Time: TimeOrd;
 
   /// Get the current time.
   fn current(&self) -> Self::Time;
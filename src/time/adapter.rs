@@ -0,0 +1,733 @@
+//! Time generator adapters.
+//!
+//! Adapters wrap a [`TimeGenerator`] and transform the time it generates; they are time generators
+//! themselves, so they can be nested and given to schedulers directly. The [`TimeGeneratorExt`]
+//! extension trait, implemented for all time generators, builds them fluently:
+//!
+//! ```
+//! use awoo::time::TimeGenerator;
+//! use awoo::time::adapter::TimeGeneratorExt;
+//! use awoo::time::simple::SimpleTimeGenerator;
+//!
+//! // play twice as fast, starting at 12s, looping over 12..20s
+//! let mut gen = SimpleTimeGenerator::new(0., 1.).scale(2.).offset(12.).looping(12., 20.);
+//!
+//! for _ in 0 .. 4 {
+//!   gen.tick();
+//! }
+//!
+//! assert_eq!(gen.current(), 12.);
+//! ```
+//!
+//! The generated time is transformed, but so is time given back to the adapters: setting an
+//! [`Offset`] to `t` sets the wrapped generator to `t - offset`, for instance.
+//!
+//! [`TimeGenerator`]: crate::time::TimeGenerator
+//! [`TimeGeneratorExt`]: crate::time::adapter::TimeGeneratorExt
+//! [`Offset`]: crate::time::adapter::Offset
+
+use std::cmp::Ordering;
+
+use crate::easing::Easing;
+use crate::time::{Arithmetic, Normalize, Scalable, TimeGenerator, TimeOrd};
+use crate::track::{Interpolate, Interpolation, Track};
+
+macro_rules! impl_adapter_inner {
+  ($($adapter:ident),*) => {
+    $(
+      impl<G> $adapter<G> where G: TimeGenerator {
+        /// Wrapped time generator.
+        pub fn inner(&self) -> &G {
+          &self.time_gen
+        }
+
+        /// Wrapped time generator.
+        pub fn inner_mut(&mut self) -> &mut G {
+          &mut self.time_gen
+        }
+
+        /// Unwrap the time generator.
+        pub fn into_inner(self) -> G {
+          self.time_gen
+        }
+      }
+    )*
+  }
+}
+
+impl_adapter_inner!(Offset, Scale, Reverse, Clamp, Loop, PingPong);
+
+/// Shift time by a constant offset.
+pub struct Offset<G> where G: TimeGenerator {
+  time_gen: G,
+  offset: G::Time
+}
+
+impl<G> Offset<G> where G: TimeGenerator, G::Time: Arithmetic {
+  /// Shift the time of `time_gen` by `offset`.
+  pub fn new(time_gen: G, offset: G::Time) -> Self {
+    Offset {
+      time_gen,
+      offset
+    }
+  }
+
+  /// Offset applied to time.
+  pub fn offset(&self) -> G::Time {
+    self.offset
+  }
+}
+
+impl<G> TimeGenerator for Offset<G> where G: TimeGenerator, G::Time: Arithmetic {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.time_gen.current().add(self.offset)
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.time_gen.tick().add(self.offset)
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.time_gen.untick().add(self.offset)
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.time_gen.set(value.sub(self.offset));
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta);
+  }
+}
+
+/// Scale time by a constant factor.
+///
+/// A factor of `2.` makes time pass twice as fast. Time and deltas given back to the adapter are
+/// divided by the factor, which must then not be zero.
+pub struct Scale<G> {
+  time_gen: G,
+  factor: f64
+}
+
+impl<G> Scale<G> where G: TimeGenerator, G::Time: Scalable {
+  /// Scale the time of `time_gen` by `factor`.
+  pub fn new(time_gen: G, factor: f64) -> Self {
+    Scale {
+      time_gen,
+      factor
+    }
+  }
+
+  /// Factor applied to time.
+  pub fn factor(&self) -> f64 {
+    self.factor
+  }
+
+  /// Change the factor applied to time.
+  pub fn set_factor(&mut self, factor: f64) {
+    self.factor = factor;
+  }
+}
+
+impl<G> TimeGenerator for Scale<G> where G: TimeGenerator, G::Time: Scalable {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.time_gen.current().scale(self.factor)
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.time_gen.tick().scale(self.factor)
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.time_gen.untick().scale(self.factor)
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.time_gen.set(value.scale(self.factor.recip()));
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta.scale(self.factor.recip()));
+  }
+}
+
+/// Reverse the direction of time: ticking unticks the wrapped generator and vice versa.
+pub struct Reverse<G> {
+  time_gen: G
+}
+
+impl<G> Reverse<G> where G: TimeGenerator {
+  /// Reverse the direction of `time_gen`.
+  pub fn new(time_gen: G) -> Self {
+    Reverse { time_gen }
+  }
+}
+
+impl<G> TimeGenerator for Reverse<G> where G: TimeGenerator {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.time_gen.current()
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.time_gen.untick()
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.time_gen.tick()
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.time_gen.set(value);
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta);
+  }
+}
+
+/// Clamp time between two bounds.
+///
+/// When time gets out of _[min; max]_, the wrapped generator is held at the bound, so that going
+/// back in the other direction takes effect immediately.
+pub struct Clamp<G> where G: TimeGenerator {
+  time_gen: G,
+  min: G::Time,
+  max: G::Time
+}
+
+impl<G> Clamp<G> where G: TimeGenerator {
+  /// Clamp the time of `time_gen` between `min` and `max`.
+  pub fn new(time_gen: G, min: G::Time, max: G::Time) -> Self {
+    let mut clamp = Clamp {
+      time_gen,
+      min,
+      max
+    };
+
+    clamp.hold();
+    clamp
+  }
+
+  fn clamp_time(&self, t: G::Time) -> G::Time {
+    if t.time_cmp(&self.min) == Ordering::Less {
+      self.min
+    } else if t.time_cmp(&self.max) == Ordering::Greater {
+      self.max
+    } else {
+      t
+    }
+  }
+
+  // bring the wrapped generator back between the bounds
+  fn hold(&mut self) {
+    let t = self.time_gen.current();
+    let clamped = self.clamp_time(t);
+
+    if clamped.time_cmp(&t) != Ordering::Equal {
+      self.time_gen.set(clamped);
+    }
+  }
+}
+
+impl<G> TimeGenerator for Clamp<G> where G: TimeGenerator {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.clamp_time(self.time_gen.current())
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    let t = self.time_gen.tick();
+    self.hold();
+    self.clamp_time(t)
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    let t = self.time_gen.untick();
+    self.hold();
+    self.clamp_time(t)
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+    self.hold();
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    let value = self.clamp_time(value);
+    self.time_gen.set(value);
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta);
+  }
+}
+
+// wrap t into [start; start + period)
+fn wrap<T>(t: T, start: T, period: T) -> T where T: Arithmetic {
+  let end = start.add(period);
+  let wrapped = if t.time_cmp(&start) == Ordering::Less {
+    let r = start.sub(t).rem(period);
+
+    if r.time_cmp(&T::zero()) == Ordering::Equal {
+      start
+    } else {
+      end.sub(r)
+    }
+  } else {
+    start.add(t.sub(start).rem(period))
+  };
+
+  // rounding errors or NaN might put the wrapped time out of the loop
+  if wrapped.time_cmp(&start) != Ordering::Less && wrapped.time_cmp(&end) == Ordering::Less {
+    wrapped
+  } else {
+    start
+  }
+}
+
+/// Loop time over _[start; end)_.
+///
+/// When time reaches `end`, it goes back to `start`; ticking backwards before `start` goes to the
+/// end of the loop.
+pub struct Loop<G> where G: TimeGenerator {
+  time_gen: G,
+  start: G::Time,
+  period: G::Time
+}
+
+impl<G> Loop<G> where G: TimeGenerator, G::Time: Arithmetic {
+  /// Loop the time of `time_gen` over _[start; end)_.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `end` is not greater than `start`.
+  pub fn new(time_gen: G, start: G::Time, end: G::Time) -> Self {
+    assert!(end.time_cmp(&start) == Ordering::Greater, "empty loop");
+
+    let mut looping = Loop {
+      time_gen,
+      start,
+      period: end.sub(start)
+    };
+
+    looping.wrap_inner();
+    looping
+  }
+
+  fn wrap_inner(&mut self) {
+    let t = self.time_gen.current();
+    let wrapped = wrap(t, self.start, self.period);
+
+    if wrapped.time_cmp(&t) != Ordering::Equal {
+      self.time_gen.set(wrapped);
+    }
+  }
+}
+
+impl<G> TimeGenerator for Loop<G> where G: TimeGenerator, G::Time: Arithmetic {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    wrap(self.time_gen.current(), self.start, self.period)
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    let t = self.time_gen.tick();
+    self.wrap_inner();
+    wrap(t, self.start, self.period)
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    let t = self.time_gen.untick();
+    self.wrap_inner();
+    wrap(t, self.start, self.period)
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+    self.wrap_inner();
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.time_gen.set(wrap(value, self.start, self.period));
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta);
+  }
+}
+
+/// Go back and forth over _[start; end]_.
+///
+/// Time goes from `start` to `end`, then back to `start`, and so on.
+pub struct PingPong<G> where G: TimeGenerator {
+  time_gen: G,
+  start: G::Time,
+  end: G::Time
+}
+
+impl<G> PingPong<G> where G: TimeGenerator, G::Time: Arithmetic {
+  /// Make the time of `time_gen` go back and forth over _[start; end]_.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `end` is not greater than `start`.
+  pub fn new(time_gen: G, start: G::Time, end: G::Time) -> Self {
+    assert!(end.time_cmp(&start) == Ordering::Greater, "empty ping-pong");
+
+    let mut ping_pong = PingPong {
+      time_gen,
+      start,
+      end
+    };
+
+    ping_pong.wrap_inner();
+    ping_pong
+  }
+
+  // the wrapped generator runs over [start; start + 2 * (end - start)); the second half is mirrored
+  fn period(&self) -> G::Time {
+    self.end.sub(self.start).times(2)
+  }
+
+  fn mirror(&self, t: G::Time) -> G::Time {
+    let t = wrap(t, self.start, self.period());
+
+    if t.time_cmp(&self.end) == Ordering::Greater {
+      self.end.sub(t.sub(self.end))
+    } else {
+      t
+    }
+  }
+
+  fn wrap_inner(&mut self) {
+    let t = self.time_gen.current();
+    let wrapped = wrap(t, self.start, self.period());
+
+    if wrapped.time_cmp(&t) != Ordering::Equal {
+      self.time_gen.set(wrapped);
+    }
+  }
+}
+
+impl<G> TimeGenerator for PingPong<G> where G: TimeGenerator, G::Time: Arithmetic {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.mirror(self.time_gen.current())
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    let t = self.time_gen.tick();
+    self.wrap_inner();
+    self.mirror(t)
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    let t = self.time_gen.untick();
+    self.wrap_inner();
+    self.mirror(t)
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+    self.wrap_inner();
+  }
+
+  /// Set the time, in the forward direction; values out of _[start; end]_ are clamped.
+  fn set(&mut self, value: Self::Time) {
+    let value = if value.time_cmp(&self.end) == Ordering::Greater {
+      self.end
+    } else if value.time_cmp(&self.start) == Ordering::Less {
+      self.start
+    } else {
+      value
+    };
+
+    self.time_gen.set(wrap(value, self.start, self.period()));
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta);
+  }
+}
+
+/// Curves mapping time to time.
+///
+/// Curves are used to remap time with a [`Warp`] adapter.
+pub trait Curve<T> {
+  /// Map a time through the curve.
+  fn map(&self, t: T) -> T;
+
+  /// Find the time that the curve maps to `value`, if any.
+  ///
+  /// Curves that can’t be inverted return [`None`]; that is the default.
+  fn inverse(&self, value: T) -> Option<T> {
+    let _ = value;
+    None
+  }
+}
+
+impl<T, F> Curve<T> for F where F: Fn(T) -> T {
+  fn map(&self, t: T) -> T {
+    self(t)
+  }
+}
+
+/// Keyframed curves.
+///
+/// Before the first key and after the last one, time is held at the value of the first and last
+/// keys. The curve can be inverted only if the values of its keys are non-decreasing and its
+/// interpolation modes are monotonic (see [`Interpolation::is_monotonic`]); values jumped over by
+/// [`Interpolation::Step`] keys can’t be inverted.
+///
+/// [`Interpolation::Step`]: crate::track::Interpolation::Step
+/// [`Interpolation::is_monotonic`]: crate::track::Interpolation::is_monotonic
+impl<T> Curve<T> for Track<T, T> where T: Interpolate + Normalize + TimeOrd {
+  fn map(&self, t: T) -> T {
+    self.sample(t).unwrap_or(t)
+  }
+
+  fn inverse(&self, value: T) -> Option<T> {
+    let keys = self.keys();
+    let (first, last) = (keys.first()?, keys.last()?);
+
+    // bisection requires the curve to be non-decreasing
+    let monotonic = keys.windows(2).all(|pair| {
+      pair[0].interpolation.is_monotonic() && pair[0].value.time_cmp(&pair[1].value) != Ordering::Greater
+    });
+
+    if !monotonic {
+      return None;
+    }
+
+    if value.time_cmp(&first.value) == Ordering::Less || value.time_cmp(&last.value) == Ordering::Greater {
+      return None;
+    }
+
+    // index of the first key with a value above the searched one
+    let i = keys.partition_point(|key| key.value.time_cmp(&value) != Ordering::Greater);
+
+    if i == keys.len() || keys[i - 1].value.time_cmp(&value) == Ordering::Equal {
+      return Some(keys[i - 1].t);
+    }
+
+    // steps jump over the values between their key and the next one
+    if let Interpolation::Step = keys[i - 1].interpolation {
+      return None;
+    }
+
+    Some(bisect(keys[i - 1].t, keys[i].t, value, |t| self.map(t)))
+  }
+}
+
+/// A curve easing time between two bounds.
+///
+/// Between `start` and `end`, time is remapped with an easing function, so that it still starts at
+/// `start` and ends at `end`; outside of the bounds, time is left untouched. That is typically used
+/// for speed ramps. The curve can be inverted if the easing function is monotonic (see
+/// [`Easing::is_monotonic`]).
+///
+/// [`Easing::is_monotonic`]: crate::easing::Easing::is_monotonic
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EasingCurve<T> {
+  start: T,
+  end: T,
+  easing: Easing
+}
+
+impl<T> EasingCurve<T> where T: Interpolate + Normalize + TimeOrd {
+  /// Create a curve easing time between `start` and `end`.
+  pub fn new(start: T, end: T, easing: Easing) -> Self {
+    EasingCurve {
+      start,
+      end,
+      easing
+    }
+  }
+
+  fn contains(&self, t: T) -> bool {
+    t.time_cmp(&self.start) == Ordering::Greater && t.time_cmp(&self.end) == Ordering::Less
+  }
+}
+
+impl<T> Curve<T> for EasingCurve<T> where T: Interpolate + Normalize + TimeOrd {
+  fn map(&self, t: T) -> T {
+    if !self.contains(t) {
+      return t;
+    }
+
+    let progress = (t - self.start).normalize(self.end - self.start);
+    T::lerp(self.start, self.end, self.easing.ease(progress))
+  }
+
+  fn inverse(&self, value: T) -> Option<T> {
+    if !self.contains(value) {
+      Some(value)
+    } else if self.easing.is_monotonic() {
+      Some(bisect(self.start, self.end, value, |t| self.map(t)))
+    } else {
+      None
+    }
+  }
+}
+
+// find t in [lo; hi] such that f(t) = target, f being non-decreasing
+fn bisect<T, F>(mut lo: T, mut hi: T, target: T, f: F) -> T where T: Interpolate + TimeOrd, F: Fn(T) -> T {
+  for _ in 0 .. 64 {
+    let mid = T::lerp(lo, hi, 0.5);
+
+    match f(mid).time_cmp(&target) {
+      Ordering::Less => lo = mid,
+      Ordering::Greater => hi = mid,
+      Ordering::Equal => return mid
+    }
+  }
+
+  T::lerp(lo, hi, 0.5)
+}
+
+/// Remap time through a [`Curve`].
+///
+/// That is useful for slow-motion or speed-ramp effects: the scheduler sees the time of the wrapped
+/// generator mapped through the curve. Setting the time inverts the curve to position the wrapped
+/// generator; deltas are passed as-is and are then expressed in the time of the wrapped generator.
+pub struct Warp<G, C> {
+  time_gen: G,
+  curve: C
+}
+
+impl<G, C> Warp<G, C> where G: TimeGenerator, C: Curve<G::Time> {
+  /// Remap the time of `time_gen` through `curve`.
+  pub fn new(time_gen: G, curve: C) -> Self {
+    Warp {
+      time_gen,
+      curve
+    }
+  }
+
+  /// Wrapped time generator.
+  pub fn inner(&self) -> &G {
+    &self.time_gen
+  }
+
+  /// Wrapped time generator.
+  pub fn inner_mut(&mut self) -> &mut G {
+    &mut self.time_gen
+  }
+
+  /// Unwrap the time generator.
+  pub fn into_inner(self) -> G {
+    self.time_gen
+  }
+
+  /// Curve used to remap time.
+  pub fn curve(&self) -> &C {
+    &self.curve
+  }
+
+  /// Try to set the time.
+  ///
+  /// If the curve can’t be inverted at `value`, the wrapped generator is left untouched and `false`
+  /// is returned.
+  pub fn try_set(&mut self, value: G::Time) -> bool {
+    match self.curve.inverse(value) {
+      Some(t) => {
+        self.time_gen.set(t);
+        true
+      }
+
+      None => false
+    }
+  }
+}
+
+impl<G, C> TimeGenerator for Warp<G, C> where G: TimeGenerator, C: Curve<G::Time> {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.curve.map(self.time_gen.current())
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.curve.map(self.time_gen.tick())
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.curve.map(self.time_gen.untick())
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+  }
+
+  /// Set the time, if the curve can be inverted at `value` (see [`Warp::try_set`]).
+  fn set(&mut self, value: Self::Time) {
+    self.try_set(value);
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(delta);
+  }
+}
+
+/// Fluent construction of time generator adapters.
+///
+/// That trait is implemented for all time generators.
+pub trait TimeGeneratorExt: TimeGenerator + Sized {
+  /// Shift time by `offset` (see [`Offset`]).
+  fn offset(self, offset: Self::Time) -> Offset<Self> where Self::Time: Arithmetic {
+    Offset::new(self, offset)
+  }
+
+  /// Scale time by `factor` (see [`Scale`]).
+  fn scale(self, factor: f64) -> Scale<Self> where Self::Time: Scalable {
+    Scale::new(self, factor)
+  }
+
+  /// Reverse the direction of time (see [`Reverse`]).
+  fn reverse(self) -> Reverse<Self> {
+    Reverse::new(self)
+  }
+
+  /// Clamp time between `min` and `max` (see [`Clamp`]).
+  fn clamp(self, min: Self::Time, max: Self::Time) -> Clamp<Self> {
+    Clamp::new(self, min, max)
+  }
+
+  /// Loop time over _[start; end)_ (see [`Loop`]).
+  fn looping(self, start: Self::Time, end: Self::Time) -> Loop<Self> where Self::Time: Arithmetic {
+    Loop::new(self, start, end)
+  }
+
+  /// Go back and forth over _[start; end]_ (see [`PingPong`]).
+  fn ping_pong(self, start: Self::Time, end: Self::Time) -> PingPong<Self> where Self::Time: Arithmetic {
+    PingPong::new(self, start, end)
+  }
+
+  /// Remap time through `curve` (see [`Warp`]).
+  fn warp<C>(self, curve: C) -> Warp<Self, C> where C: Curve<Self::Time> {
+    Warp::new(self, curve)
+  }
+}
+
+impl<G> TimeGeneratorExt for G where G: TimeGenerator {}
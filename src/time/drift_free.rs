@@ -0,0 +1,79 @@
+//! The drift-free time generator.
+
+use crate::time::{Arithmetic, TimeGenerator};
+
+/// A [`TimeGenerator`] that generates linear time without drifting.
+///
+/// A [`SimpleTimeGenerator`] accumulates its delta at each tick, which accumulates rounding errors
+/// as well when using floating-point time: after thousands of ticks, windows might start or end
+/// one tick off. This generator counts ticks instead, and computes the current time as
+/// `origin + count * delta`, so that the error doesn’t depend on the number of ticks.
+///
+/// Setting the time or changing the delta moves the origin to the current time and resets the
+/// count.
+///
+/// [`SimpleTimeGenerator`]: crate::time::simple::SimpleTimeGenerator
+pub struct DriftFreeTimeGenerator<T> {
+  origin: T,
+  count: i64,
+  reset_value: T,
+  delta: T
+}
+
+impl<T> DriftFreeTimeGenerator<T> where T: Arithmetic {
+  /// Create a new [`DriftFreeTimeGenerator`].
+  pub fn new(reset_value: T, delta: T) -> Self {
+    DriftFreeTimeGenerator {
+      origin: reset_value,
+      count: 0,
+      reset_value,
+      delta
+    }
+  }
+
+  /// Number of ticks since the origin.
+  ///
+  /// The count is negative if the generator was unticked past its origin.
+  pub fn count(&self) -> i64 {
+    self.count
+  }
+}
+
+impl<T> TimeGenerator for DriftFreeTimeGenerator<T> where T: Arithmetic {
+  type Time = T;
+
+  fn current(&self) -> Self::Time {
+    if self.count >= 0 {
+      self.origin.add(self.delta.times(self.count as u64))
+    } else {
+      self.origin.sub(self.delta.times(self.count.unsigned_abs()))
+    }
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    let t = self.current();
+    self.count += 1;
+    t
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    let t = self.current();
+    self.count -= 1;
+    t
+  }
+
+  fn reset(&mut self) {
+    self.set(self.reset_value);
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.origin = value;
+    self.count = 0;
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.origin = self.current();
+    self.count = 0;
+    self.delta = delta;
+  }
+}
@@ -0,0 +1,189 @@
+//! Fixed-timestep time generation.
+//!
+//! Simulations (physics, for instance) are better run with a fixed timestep, while rendering
+//! happens at whatever pace frames are produced. The classic way to reconcile both is to
+//! accumulate the real time a frame took and to run as many fixed steps as fit in the accumulated
+//! time, keeping the remainder for the next frame. Renderers then use the ratio of the remainder
+//! over the fixed step (_alpha_) to interpolate between the last two simulation states.
+//!
+//! [`FixedStepTimeGenerator`] implements that pattern on top of any [`TimeGenerator`]:
+//!
+//! ```
+//! use awoo::scheduler::RandomAccessScheduler;
+//! use awoo::time::fixed_step::FixedStepTimeGenerator;
+//! use awoo::time::simple::SimpleTimeGenerator;
+//! use awoo::window::Window;
+//!
+//! let gen = FixedStepTimeGenerator::new(SimpleTimeGenerator::new(0., 0.01), 0.01, 0.25);
+//! let alpha = gen.alpha_handle();
+//! let windows = vec![
+//!   Window::new(0., 10.).map(|t| println!("simulating at {}, rendering with alpha {}", t, alpha.get()))
+//! ];
+//! let mut scheduler = RandomAccessScheduler::new(gen, windows).unwrap();
+//!
+//! // in your main loop, once per frame
+//! let frame_time = 1. / 60.;
+//! let steps = scheduler.time_gen_mut().accumulate(frame_time);
+//!
+//! for _ in 0 .. steps {
+//!   scheduler.step();
+//! }
+//! ```
+//!
+//! [`FixedStepTimeGenerator`]: crate::time::fixed_step::FixedStepTimeGenerator
+//! [`TimeGenerator`]: crate::time::TimeGenerator
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use crate::time::{Arithmetic, Normalize, TimeGenerator, TimeOrd};
+
+/// A [`TimeGenerator`] ticking with a fixed timestep, driven by real frame times.
+///
+/// Each tick moves the wrapped time generator by exactly one fixed step. Real frame times are
+/// given with [`accumulate`], which tells how many fixed steps to run for that frame.
+///
+/// To avoid the _spiral of death_ (frames taking longer because more steps are run, leading to
+/// even more steps on the next frame), frame times are clamped to a maximum value.
+///
+/// [`accumulate`]: Self::accumulate
+pub struct FixedStepTimeGenerator<G> where G: TimeGenerator {
+  time_gen: G,
+  step: G::Time,
+  max_frame_time: G::Time,
+  accumulator: G::Time,
+  alpha: Alpha
+}
+
+impl<G> FixedStepTimeGenerator<G> where G: TimeGenerator, G::Time: Arithmetic + Normalize {
+  /// Create a new [`FixedStepTimeGenerator`] wrapping a time generator.
+  ///
+  /// `step` is the fixed timestep; the delta of the wrapped time generator is changed to it.
+  /// `max_frame_time` is the greatest frame time accepted by [`accumulate`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `step` or `max_frame_time` is not a positive, finite value.
+  ///
+  /// [`accumulate`]: Self::accumulate
+  pub fn new(mut time_gen: G, step: G::Time, max_frame_time: G::Time) -> Self {
+    assert_valid_step(step);
+    assert_valid_step(max_frame_time);
+    time_gen.change_delta(step);
+
+    FixedStepTimeGenerator {
+      time_gen,
+      step,
+      max_frame_time,
+      accumulator: G::Time::zero(),
+      alpha: Alpha::default()
+    }
+  }
+
+  /// Accumulate the time a frame took and return the number of fixed steps to run.
+  ///
+  /// `frame_time` is clamped to the maximum frame time.
+  pub fn accumulate(&mut self, frame_time: G::Time) -> usize {
+    let frame_time = match frame_time.time_cmp(&self.max_frame_time) {
+      Ordering::Greater => self.max_frame_time,
+      _ => frame_time
+    };
+
+    self.accumulator = self.accumulator.add(frame_time);
+
+    // drain the accumulator by the greatest power-of-two number of steps that fits, so that large
+    // frame times don’t take as many iterations as steps
+    let mut steps = 0;
+    while self.accumulator.time_cmp(&self.step) != Ordering::Less {
+      let mut n = 1;
+
+      while n < u64::MAX / 2 && self.step.times(n * 2).time_cmp(&self.accumulator) != Ordering::Greater {
+        n *= 2;
+      }
+
+      self.accumulator = self.accumulator.sub(self.step.times(n));
+      steps += n as usize;
+    }
+
+    self.alpha.0.set(self.accumulator.normalize(self.step));
+    steps
+  }
+
+  /// Interpolation factor between the last two fixed steps, in _[0; 1)_.
+  ///
+  /// That is the ratio of the time left in the accumulator over the fixed step.
+  pub fn alpha(&self) -> f32 {
+    self.alpha.get()
+  }
+
+  /// Get a handle on the interpolation factor.
+  ///
+  /// The handle always reads the latest alpha. It’s typically captured by mapped windows actions,
+  /// since the time generator is owned by the scheduler.
+  pub fn alpha_handle(&self) -> Alpha {
+    self.alpha.clone()
+  }
+
+  /// Fixed timestep.
+  pub fn step(&self) -> G::Time {
+    self.step
+  }
+}
+
+impl<G> TimeGenerator for FixedStepTimeGenerator<G> where G: TimeGenerator, G::Time: Arithmetic + Normalize {
+  type Time = G::Time;
+
+  fn current(&self) -> Self::Time {
+    self.time_gen.current()
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.time_gen.tick()
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.time_gen.untick()
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+    self.accumulator = G::Time::zero();
+    self.alpha.0.set(0.);
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.time_gen.set(value);
+  }
+
+  /// Change the fixed timestep.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `delta` is not a positive, finite value.
+  fn change_delta(&mut self, delta: Self::Time) {
+    assert_valid_step(delta);
+    self.step = delta;
+    self.time_gen.change_delta(delta);
+  }
+}
+
+// A zero, negative or NaN step would never drain the accumulator, and neither would an infinite
+// frame time; NaN and infinite values are caught by not cancelling out when subtracted from
+// themselves.
+fn assert_valid_step<T>(step: T) where T: Arithmetic {
+  let zero = T::zero();
+  let valid = step.time_cmp(&zero) == Ordering::Greater && step.sub(step).time_cmp(&zero) == Ordering::Equal;
+  assert!(valid, "non-positive or non-finite fixed step or frame time");
+}
+
+/// Shared handle on the interpolation factor of a [`FixedStepTimeGenerator`].
+#[derive(Clone, Debug, Default)]
+pub struct Alpha(Rc<Cell<f32>>);
+
+impl Alpha {
+  /// Current interpolation factor.
+  pub fn get(&self) -> f32 {
+    self.0.get()
+  }
+}
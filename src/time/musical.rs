@@ -0,0 +1,362 @@
+//! Musical time.
+//!
+//! Demos are often synchronized with music, and it’s easier to author windows in bars and beats
+//! than in seconds. [`MusicalTime`] is a position in a song, expressed in bars, beats and ticks. A
+//! [`TempoMap`] gives the tempo and the time signature of the song, which can both change over
+//! time, and converts musical time to and from seconds.
+//!
+//! [`MusicalTimeGenerator`] wraps any time generator producing seconds and turns its time into
+//! musical time, so that windows authored in musical time can be scheduled directly:
+//!
+//! ```
+//! use awoo::scheduler::RandomAccessScheduler;
+//! use awoo::time::musical::{MusicalTime, MusicalTimeGenerator, TempoMap};
+//! use awoo::time::simple::SimpleTimeGenerator;
+//! use awoo::window::Window;
+//!
+//! // 120 BPM in 4/4, then 90 BPM from the fifth bar
+//! let mut tempo_map = TempoMap::new(480, 120., 4, 4);
+//! tempo_map.add_tempo_change(MusicalTime::new(4, 0, 0), 90.);
+//!
+//! let intro = Window::new(MusicalTime::new(0, 0, 0), MusicalTime::new(4, 0, 0)).map(|t| println!("intro: {}", t));
+//! let verse = Window::new(MusicalTime::new(4, 0, 0), MusicalTime::new(12, 0, 0)).map(|t| println!("verse: {}", t));
+//!
+//! let gen = MusicalTimeGenerator::new(SimpleTimeGenerator::new(0., 1. / 60.), tempo_map);
+//! let mut scheduler = RandomAccessScheduler::new(gen, vec![intro, verse]).unwrap();
+//! # scheduler.step();
+//! ```
+//!
+//! [`MusicalTime`]: crate::time::musical::MusicalTime
+//! [`MusicalTimeGenerator`]: crate::time::musical::MusicalTimeGenerator
+//! [`TempoMap`]: crate::time::musical::TempoMap
+
+#[cfg(feature = "serde")] use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::time::{TimeGenerator, TimeOrd};
+
+/// A position in a song.
+///
+/// All fields are zero-based: the very first tick of a song is `0.0.0`. A musical time is
+/// _normalized_ when its beat and tick are lower than the number of beats in its bar and the number
+/// of ticks in a beat; only normalized musical times are meaningfully ordered.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MusicalTime {
+  /// Bar.
+  pub bar: u32,
+  /// Beat in the bar.
+  pub beat: u32,
+  /// Tick in the beat.
+  pub tick: u32
+}
+
+impl MusicalTime {
+  /// Create a new musical time.
+  pub fn new(bar: u32, beat: u32, tick: u32) -> Self {
+    MusicalTime {
+      bar,
+      beat,
+      tick
+    }
+  }
+}
+
+impl TimeOrd for MusicalTime {
+  fn time_cmp(&self, other: &Self) -> Ordering {
+    self.cmp(other)
+  }
+}
+
+impl fmt::Display for MusicalTime {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}.{}.{}", self.bar, self.beat, self.tick)
+  }
+}
+
+/// Tempo and time signature changes of a song.
+///
+/// A tempo map has a resolution, given in ticks per quarter note (PPQ). Tempi are given in quarter
+/// notes per minute (BPM). A beat is a note of the time signature’s denominator; for instance, in
+/// 6/8, a bar has six beats of an eighth note each.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TempoMap {
+  ppq: u32,
+  tempo_changes: Vec<(MusicalTime, f64)>,
+  signature_changes: Vec<SignatureSegment>,
+  tempo_segments: Vec<TempoSegment>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SignatureSegment {
+  bar: u32,
+  numerator: u32,
+  denominator: u32,
+  // absolute tick of the first bar of the segment
+  start_tick: u64
+}
+
+impl SignatureSegment {
+  fn ticks_per_beat(&self, ppq: u32) -> u64 {
+    u64::from(ppq) * 4 / u64::from(self.denominator)
+  }
+
+  fn ticks_per_bar(&self, ppq: u32) -> u64 {
+    u64::from(self.numerator) * self.ticks_per_beat(ppq)
+  }
+}
+
+fn assert_valid_tempo(bpm: f64) {
+  assert!(bpm > 0. && bpm.is_finite(), "invalid tempo: {} BPM", bpm);
+}
+
+fn assert_valid_signature(ppq: u32, numerator: u32, denominator: u32) {
+  let whole_note_ticks = u64::from(ppq) * 4;
+
+  assert!(
+    numerator > 0 && denominator > 0 && whole_note_ticks % u64::from(denominator) == 0,
+    "invalid time signature: {}/{}",
+    numerator,
+    denominator
+  );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TempoSegment {
+  start_tick: u64,
+  start_seconds: f64,
+  bpm: f64
+}
+
+impl TempoMap {
+  /// Create a new tempo map.
+  ///
+  /// `ppq` is the number of ticks per quarter note, `bpm` the initial tempo and
+  /// `numerator / denominator` the initial time signature.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `ppq` is zero, if `bpm` is not positive and finite, if `numerator` is zero or if
+  /// `denominator` doesn’t divide `4 * ppq` (beats must be a whole number of ticks).
+  pub fn new(ppq: u32, bpm: f64, numerator: u32, denominator: u32) -> Self {
+    assert!(ppq > 0, "zero ticks per quarter note");
+    assert_valid_tempo(bpm);
+    assert_valid_signature(ppq, numerator, denominator);
+
+    let mut map = TempoMap {
+      ppq,
+      tempo_changes: vec![(MusicalTime::default(), bpm)],
+      signature_changes: vec![SignatureSegment { bar: 0, numerator, denominator, start_tick: 0 }],
+      tempo_segments: Vec::new()
+    };
+
+    map.rebuild();
+    map
+  }
+
+  /// Number of ticks per quarter note.
+  pub fn ppq(&self) -> u32 {
+    self.ppq
+  }
+
+  /// Change the tempo at a given position.
+  ///
+  /// If the tempo was already changed at that position, the change is replaced.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `bpm` is not positive and finite.
+  pub fn add_tempo_change(&mut self, at: MusicalTime, bpm: f64) {
+    assert_valid_tempo(bpm);
+
+    match self.tempo_changes.binary_search_by(|(t, _)| t.cmp(&at)) {
+      Ok(i) => self.tempo_changes[i].1 = bpm,
+      Err(i) => self.tempo_changes.insert(i, (at, bpm))
+    }
+
+    self.rebuild();
+  }
+
+  /// Change the time signature from a given bar.
+  ///
+  /// If the time signature was already changed at that bar, the change is replaced.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `numerator` is zero or if `denominator` doesn’t divide `4 * ppq`.
+  pub fn add_signature_change(&mut self, bar: u32, numerator: u32, denominator: u32) {
+    assert_valid_signature(self.ppq, numerator, denominator);
+
+    let segment = SignatureSegment { bar, numerator, denominator, start_tick: 0 };
+
+    match self.signature_changes.binary_search_by(|s| s.bar.cmp(&bar)) {
+      Ok(i) => self.signature_changes[i] = segment,
+      Err(i) => self.signature_changes.insert(i, segment)
+    }
+
+    self.rebuild();
+  }
+
+  /// Tempo, in BPM, at a given position.
+  pub fn tempo_at(&self, t: MusicalTime) -> f64 {
+    self.tempo_segment(self.time_to_ticks(t)).bpm
+  }
+
+  /// Time signature, as `(numerator, denominator)`, at a given bar.
+  pub fn signature_at(&self, bar: u32) -> (u32, u32) {
+    let segment = self.signature_segment_by_bar(bar);
+    (segment.numerator, segment.denominator)
+  }
+
+  /// Convert a musical time to an absolute number of ticks since the start of the song.
+  pub fn time_to_ticks(&self, t: MusicalTime) -> u64 {
+    let segment = self.signature_segment_by_bar(t.bar);
+
+    segment.start_tick +
+      u64::from(t.bar - segment.bar) * segment.ticks_per_bar(self.ppq) +
+      u64::from(t.beat) * segment.ticks_per_beat(self.ppq) +
+      u64::from(t.tick)
+  }
+
+  /// Convert an absolute number of ticks since the start of the song to a musical time.
+  pub fn ticks_to_time(&self, ticks: u64) -> MusicalTime {
+    let i = self.signature_changes.partition_point(|s| s.start_tick <= ticks);
+    let segment = &self.signature_changes[i.max(1) - 1];
+    let ticks_per_bar = segment.ticks_per_bar(self.ppq);
+    let ticks_per_beat = segment.ticks_per_beat(self.ppq);
+    let rel = ticks - segment.start_tick;
+    let in_bar = rel % ticks_per_bar;
+
+    MusicalTime {
+      bar: segment.bar + (rel / ticks_per_bar) as u32,
+      beat: (in_bar / ticks_per_beat) as u32,
+      tick: (in_bar % ticks_per_beat) as u32
+    }
+  }
+
+  /// Convert a musical time to seconds.
+  pub fn time_to_seconds(&self, t: MusicalTime) -> f64 {
+    let ticks = self.time_to_ticks(t);
+    let segment = self.tempo_segment(ticks);
+
+    segment.start_seconds + (ticks - segment.start_tick) as f64 * self.seconds_per_tick(segment.bpm)
+  }
+
+  /// Convert seconds to a musical time.
+  ///
+  /// The result is rounded down to the tick. Negative seconds give the start of the song.
+  pub fn seconds_to_time(&self, seconds: f64) -> MusicalTime {
+    let seconds = seconds.max(0.);
+    let i = self.tempo_segments.partition_point(|s| s.start_seconds <= seconds);
+    let segment = &self.tempo_segments[i.max(1) - 1];
+
+    // the small bias prevents values such as 479.99999 ticks from being rounded down to 479
+    let ticks = ((seconds - segment.start_seconds) / self.seconds_per_tick(segment.bpm) + 1e-6).floor();
+
+    self.ticks_to_time(segment.start_tick + ticks as u64)
+  }
+
+  fn seconds_per_tick(&self, bpm: f64) -> f64 {
+    60. / (bpm * f64::from(self.ppq))
+  }
+
+  fn signature_segment_by_bar(&self, bar: u32) -> &SignatureSegment {
+    let i = self.signature_changes.partition_point(|s| s.bar <= bar);
+    &self.signature_changes[i.max(1) - 1]
+  }
+
+  fn tempo_segment(&self, ticks: u64) -> &TempoSegment {
+    let i = self.tempo_segments.partition_point(|s| s.start_tick <= ticks);
+    &self.tempo_segments[i.max(1) - 1]
+  }
+
+  // recompute the cached absolute positions of signature and tempo changes
+  fn rebuild(&mut self) {
+    let ppq = self.ppq;
+    let mut start_tick = 0;
+    let mut prev: Option<SignatureSegment> = None;
+
+    for segment in &mut self.signature_changes {
+      if let Some(prev) = prev {
+        start_tick += u64::from(segment.bar - prev.bar) * prev.ticks_per_bar(ppq);
+      }
+
+      segment.start_tick = start_tick;
+      prev = Some(*segment);
+    }
+
+    let mut segments: Vec<TempoSegment> = Vec::with_capacity(self.tempo_changes.len());
+
+    for &(at, bpm) in &self.tempo_changes {
+      let start_tick = self.time_to_ticks(at);
+      let start_seconds = match segments.last() {
+        Some(prev) => prev.start_seconds + (start_tick - prev.start_tick) as f64 * self.seconds_per_tick(prev.bpm),
+        None => 0.
+      };
+
+      segments.push(TempoSegment { start_tick, start_seconds, bpm });
+    }
+
+    self.tempo_segments = segments;
+  }
+}
+
+/// A [`TimeGenerator`] generating musical time.
+///
+/// It wraps a time generator producing seconds and converts its time through a [`TempoMap`].
+/// Setting the time converts it back to seconds. The delta, in musical time, is converted to
+/// seconds from the start of the song, so it’s only meaningful for a constant tempo; you should
+/// rather change the delta of the wrapped time generator.
+pub struct MusicalTimeGenerator<G> {
+  time_gen: G,
+  tempo_map: TempoMap
+}
+
+impl<G> MusicalTimeGenerator<G> where G: TimeGenerator<Time = f64> {
+  /// Create a new [`MusicalTimeGenerator`].
+  pub fn new(time_gen: G, tempo_map: TempoMap) -> Self {
+    MusicalTimeGenerator {
+      time_gen,
+      tempo_map
+    }
+  }
+
+  /// Current time, in seconds.
+  pub fn seconds(&self) -> f64 {
+    self.time_gen.current()
+  }
+
+  /// Tempo map used to convert time.
+  pub fn tempo_map(&self) -> &TempoMap {
+    &self.tempo_map
+  }
+}
+
+impl<G> TimeGenerator for MusicalTimeGenerator<G> where G: TimeGenerator<Time = f64> {
+  type Time = MusicalTime;
+
+  fn current(&self) -> Self::Time {
+    self.tempo_map.seconds_to_time(self.time_gen.current())
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.tempo_map.seconds_to_time(self.time_gen.tick())
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.tempo_map.seconds_to_time(self.time_gen.untick())
+  }
+
+  fn reset(&mut self) {
+    self.time_gen.reset();
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.time_gen.set(self.tempo_map.time_to_seconds(value));
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.time_gen.change_delta(self.tempo_map.time_to_seconds(delta));
+  }
+}
@@ -0,0 +1,434 @@
+//! Exact rational time.
+//!
+//! Floating-point deltas can’t represent most fractions of a second exactly: summing `1. / 30.`
+//! thirty times doesn’t give exactly `1.`, so actions close to window boundaries sometimes run one
+//! tick in the wrong window. [`Rational`] represents time as an exact fraction, so that such
+//! boundaries always line up:
+//!
+//! ```
+//! use awoo::time::TimeGenerator;
+//! use awoo::time::rational::Rational;
+//! use awoo::time::simple::SimpleTimeGenerator;
+//!
+//! let mut gen = SimpleTimeGenerator::new(Rational::from(0), Rational::new(1, 30));
+//!
+//! for _ in 0 .. 30 {
+//!   gen.tick();
+//! }
+//!
+//! assert_eq!(gen.current(), Rational::from(1));
+//! ```
+//!
+//! [`Rational`]: crate::time::rational::Rational
+
+#[cfg(feature = "serde")] use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::time::{Arithmetic, Normalize, Scalable, TimeOrd};
+
+/// An exact fraction `numerator / denominator`.
+///
+/// Rationals are always normalized: the denominator is positive and the fraction is irreducible, so
+/// that equal rationals have equal numerators and denominators. Operations are computed exactly;
+/// if the result doesn’t fit in 64-bit integers, it’s approximated by the closest fraction that
+/// does.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Rational {
+  num: i64,
+  den: i64
+}
+
+impl Rational {
+  /// Create a new rational.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `den` is zero.
+  pub fn new(num: i64, den: i64) -> Self {
+    assert!(den != 0, "rational with a zero denominator");
+    Rational::from_i128(i128::from(num), i128::from(den))
+  }
+
+  /// Numerator.
+  pub fn numer(&self) -> i64 {
+    self.num
+  }
+
+  /// Denominator (always positive).
+  pub fn denom(&self) -> i64 {
+    self.den
+  }
+
+  /// Convert to the closest `f64`.
+  pub fn to_f64(self) -> f64 {
+    self.num as f64 / self.den as f64
+  }
+
+  /// Convert to the closest `f32`.
+  pub fn to_f32(self) -> f32 {
+    self.to_f64() as f32
+  }
+
+  /// Find the closest rational to `x` with a denominator lower or equal to `max_den`.
+  ///
+  /// That is useful to recover “nice” fractions from floats, as `1. / 3.` is not exactly a third.
+  /// Return [`None`] if `x` is not finite or doesn’t fit, or if `max_den` is not positive.
+  pub fn approximate(x: f64, max_den: i64) -> Option<Self> {
+    if !x.is_finite() || max_den <= 0 || x.abs() >= i64::MAX as f64 {
+      return None;
+    }
+
+    // continued fraction expansion; (p0 / q0) and (p1 / q1) are the last two convergents
+    let (mut p0, mut q0, mut p1, mut q1) = (0i128, 1i128, 1i128, 0i128);
+    let mut r = x;
+
+    loop {
+      let a = r.floor();
+      let ai = a as i128;
+      let (p2, q2) = (ai * p1 + p0, ai * q1 + q0);
+
+      if q2 > i128::from(max_den) {
+        // best semiconvergent with an admissible denominator
+        let k = (i128::from(max_den) - q0) / q1;
+        let (ps, qs) = (k * p1 + p0, k * q1 + q0);
+        let semi = Rational::from_i128(ps, qs);
+        let conv = Rational::from_i128(p1, q1);
+
+        return Some(if (semi.to_f64() - x).abs() < (conv.to_f64() - x).abs() { semi } else { conv });
+      }
+
+      p0 = p1;
+      q0 = q1;
+      p1 = p2;
+      q1 = q2;
+
+      let frac = r - a;
+
+      if frac == 0. || (p1 as f64 / q1 as f64) == x {
+        return Some(Rational::from_i128(p1, q1));
+      }
+
+      r = 1. / frac;
+    }
+  }
+
+  // normalize a fraction computed with wider integers, approximating it if it doesn’t fit
+  fn from_i128(mut num: i128, mut den: i128) -> Self {
+    if den < 0 {
+      num = -num;
+      den = -den;
+    }
+
+    let g = gcd(num.unsigned_abs(), den.unsigned_abs()) as i128;
+
+    if g > 1 {
+      num /= g;
+      den /= g;
+    }
+
+    let limit = i128::from(i64::MAX);
+
+    if num.abs() > limit || den > limit {
+      let scale = (num.abs().max(den) + limit - 1) / limit;
+      let den = (den / scale).max(1);
+      let num = (num / scale).clamp(-limit, limit);
+      let g = gcd(num.unsigned_abs(), den.unsigned_abs()).max(1) as i128;
+
+      return Rational {
+        num: (num / g) as i64,
+        den: (den / g) as i64
+      };
+    }
+
+    Rational {
+      num: num as i64,
+      den: den as i64
+    }
+  }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+  while b != 0 {
+    let r = a % b;
+    a = b;
+    b = r;
+  }
+
+  a
+}
+
+impl Default for Rational {
+  fn default() -> Self {
+    Rational { num: 0, den: 1 }
+  }
+}
+
+impl From<i64> for Rational {
+  fn from(n: i64) -> Self {
+    Rational { num: n, den: 1 }
+  }
+}
+
+impl From<i32> for Rational {
+  fn from(n: i32) -> Self {
+    Rational::from(i64::from(n))
+  }
+}
+
+impl From<Rational> for f64 {
+  fn from(r: Rational) -> Self {
+    r.to_f64()
+  }
+}
+
+impl From<Rational> for f32 {
+  fn from(r: Rational) -> Self {
+    r.to_f32()
+  }
+}
+
+impl TryFrom<f64> for Rational {
+  type Error = RationalError;
+
+  /// Convert a float exactly.
+  ///
+  /// Floats are binary fractions, so the conversion is exact as long as the numerator and
+  /// denominator fit. Use [`Rational::approximate`] to get simpler fractions.
+  fn try_from(x: f64) -> Result<Self, Self::Error> {
+    if !x.is_finite() {
+      return Err(RationalError::NotFinite);
+    }
+
+    if x == 0. {
+      return Ok(Rational::default());
+    }
+
+    // x = mantissa * 2^exp
+    let bits = x.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7ff) as i32;
+    let (mut mantissa, mut exp) = if biased_exp == 0 {
+      ((bits & 0xf_ffff_ffff_ffff) as i128, -1074)
+    } else {
+      (((bits & 0xf_ffff_ffff_ffff) | (1 << 52)) as i128, biased_exp - 1075)
+    };
+
+    let zeros = mantissa.trailing_zeros() as i32;
+    mantissa >>= zeros;
+    exp += zeros;
+
+    if x < 0. {
+      mantissa = -mantissa;
+    }
+
+    if exp >= 0 {
+      if exp >= 63 || mantissa.abs() << exp > i128::from(i64::MAX) {
+        return Err(RationalError::Overflow);
+      }
+
+      Ok(Rational::from_i128(mantissa << exp, 1))
+    } else if exp > -63 {
+      Ok(Rational::from_i128(mantissa, 1 << -exp))
+    } else {
+      Err(RationalError::Overflow)
+    }
+  }
+}
+
+impl TryFrom<f32> for Rational {
+  type Error = RationalError;
+
+  fn try_from(x: f32) -> Result<Self, Self::Error> {
+    Rational::try_from(f64::from(x))
+  }
+}
+
+impl PartialOrd for Rational {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Rational {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (i128::from(self.num) * i128::from(other.den)).cmp(&(i128::from(other.num) * i128::from(self.den)))
+  }
+}
+
+impl TimeOrd for Rational {
+  fn time_cmp(&self, other: &Self) -> Ordering {
+    self.cmp(other)
+  }
+}
+
+impl Add for Rational {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self {
+    let (a, b, c, d) = (i128::from(self.num), i128::from(self.den), i128::from(rhs.num), i128::from(rhs.den));
+    Rational::from_i128(a * d + c * b, b * d)
+  }
+}
+
+impl Sub for Rational {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self {
+    self + -rhs
+  }
+}
+
+impl Mul for Rational {
+  type Output = Self;
+
+  fn mul(self, rhs: Self) -> Self {
+    Rational::from_i128(
+      i128::from(self.num) * i128::from(rhs.num),
+      i128::from(self.den) * i128::from(rhs.den)
+    )
+  }
+}
+
+impl Div for Rational {
+  type Output = Self;
+
+  /// # Panics
+  ///
+  /// Panics if `rhs` is zero.
+  fn div(self, rhs: Self) -> Self {
+    assert!(rhs.num != 0, "division of a rational by zero");
+
+    Rational::from_i128(
+      i128::from(self.num) * i128::from(rhs.den),
+      i128::from(self.den) * i128::from(rhs.num)
+    )
+  }
+}
+
+impl Neg for Rational {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Rational::from_i128(-i128::from(self.num), i128::from(self.den))
+  }
+}
+
+impl Arithmetic for Rational {
+  fn zero() -> Self {
+    Rational::default()
+  }
+
+  fn add(self, rhs: Self) -> Self {
+    self + rhs
+  }
+
+  fn sub(self, rhs: Self) -> Self {
+    self - rhs
+  }
+
+  fn times(self, n: u64) -> Self {
+    Rational::from_i128(i128::from(self.num) * i128::from(n), i128::from(self.den))
+  }
+
+  fn rem(self, rhs: Self) -> Self {
+    // both rationals over the common denominator self.den * rhs.den
+    let a = i128::from(self.num) * i128::from(rhs.den);
+    let b = i128::from(rhs.num) * i128::from(self.den);
+
+    Rational::from_i128(a.rem_euclid(b), i128::from(self.den) * i128::from(rhs.den))
+  }
+}
+
+impl Normalize for Rational {
+  fn normalize(self, length: Self) -> f32 {
+    (self / length).to_f32()
+  }
+}
+
+impl Scalable for Rational {
+  /// The factor is first approximated by a fraction with a denominator up to one million, so that
+  /// common factors (such as `0.5` or `1.5`) are exact. A non-finite factor leaves the time as-is.
+  fn scale(self, factor: f64) -> Self {
+    Rational::approximate(factor, 1_000_000).map_or(self, |factor| self * factor)
+  }
+}
+
+impl fmt::Display for Rational {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.den == 1 {
+      write!(f, "{}", self.num)
+    } else {
+      write!(f, "{}/{}", self.num, self.den)
+    }
+  }
+}
+
+impl FromStr for Rational {
+  type Err = RationalError;
+
+  /// Parse a rational of the form `num/den` or `num`.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let malformed = || RationalError::Malformed(s.to_owned());
+
+    match s.split_once('/') {
+      Some((num, den)) => {
+        let num = num.trim().parse().map_err(|_| malformed())?;
+        let den = den.trim().parse().map_err(|_| malformed())?;
+
+        if den == 0 {
+          Err(RationalError::ZeroDenominator)
+        } else {
+          Ok(Rational::new(num, den))
+        }
+      }
+
+      None => s.trim().parse::<i64>().map(Rational::from).map_err(|_| malformed())
+    }
+  }
+}
+
+impl TryFrom<String> for Rational {
+  type Error = RationalError;
+
+  fn try_from(s: String) -> Result<Self, Self::Error> {
+    s.parse()
+  }
+}
+
+impl From<Rational> for String {
+  fn from(r: Rational) -> Self {
+    r.to_string()
+  }
+}
+
+/// Errors that might occur when converting to a [`Rational`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum RationalError {
+  /// The string is not of the form `num/den` or `num`.
+  Malformed(String),
+  /// The denominator is zero.
+  ZeroDenominator,
+  /// The float is infinite or `NaN`.
+  NotFinite,
+  /// The float doesn’t fit in 64-bit numerator and denominator.
+  Overflow
+}
+
+impl fmt::Display for RationalError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      RationalError::Malformed(ref s) => write!(f, "malformed rational: {}", s),
+      RationalError::ZeroDenominator => f.write_str("rational with a zero denominator"),
+      RationalError::NotFinite => f.write_str("float is not finite"),
+      RationalError::Overflow => f.write_str("float doesn’t fit in a rational")
+    }
+  }
+}
+
+impl Error for RationalError {}
@@ -0,0 +1,188 @@
+//! The audio-sample clock time generator.
+//!
+//! When the audio thread is the master clock, scheduling must follow the number of samples actually
+//! played rather than the wall clock. The audio callback advances a shared [`SampleCounter`]; a
+//! [`SampleClockTimeGenerator`] reads it and derives time from the sample rate, either in seconds
+//! ([`Seconds`]) or in raw samples ([`Samples`]):
+//!
+//! ```
+//! use awoo::time::TimeGenerator;
+//! use awoo::time::sample_clock::{SampleClockTimeGenerator, SampleCounter};
+//!
+//! let counter = SampleCounter::new();
+//! let gen = SampleClockTimeGenerator::new(counter.clone(), 48_000);
+//!
+//! // in the audio callback, after having played a buffer of 480 frames
+//! counter.advance(480);
+//!
+//! assert_eq!(gen.current(), 0.01);
+//! ```
+//!
+//! [`SampleCounter`]: crate::time::sample_clock::SampleCounter
+//! [`SampleClockTimeGenerator`]: crate::time::sample_clock::SampleClockTimeGenerator
+//! [`Seconds`]: crate::time::sample_clock::Seconds
+//! [`Samples`]: crate::time::sample_clock::Samples
+
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::time::{TimeGenerator, TimeOrd};
+
+/// A sample counter, shared between the audio thread and time generators.
+///
+/// Cloning a counter gives another handle to the same counter.
+#[derive(Clone, Debug, Default)]
+pub struct SampleCounter {
+  // the counter doesn’t guard any other memory, so relaxed atomic operations are enough
+  samples: Arc<AtomicU64>
+}
+
+impl SampleCounter {
+  /// Create a new counter, starting at zero.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Number of samples played so far.
+  pub fn get(&self) -> u64 {
+    self.samples.load(Ordering::Relaxed)
+  }
+
+  /// Set the number of samples played so far (when the audio stream seeks, for instance).
+  pub fn set(&self, samples: u64) {
+    self.samples.store(samples, Ordering::Relaxed);
+  }
+
+  /// Advance the counter by a number of samples (frames).
+  pub fn advance(&self, samples: u64) {
+    self.samples.fetch_add(samples, Ordering::Relaxed);
+  }
+}
+
+/// Units in which a [`SampleClockTimeGenerator`] expresses time.
+pub trait SampleUnit {
+  /// Type of time.
+  type Time: TimeOrd;
+
+  /// Convert a sample position to time.
+  fn from_samples(samples: u64, sample_rate: u32) -> Self::Time;
+
+  /// Convert time to a sample position.
+  fn to_samples(time: Self::Time, sample_rate: u32) -> i64;
+}
+
+/// Time in seconds (`f64`).
+#[derive(Clone, Copy, Debug)]
+pub struct Seconds;
+
+impl SampleUnit for Seconds {
+  type Time = f64;
+
+  fn from_samples(samples: u64, sample_rate: u32) -> Self::Time {
+    samples as f64 / f64::from(sample_rate)
+  }
+
+  fn to_samples(time: Self::Time, sample_rate: u32) -> i64 {
+    (time * f64::from(sample_rate)).round() as i64
+  }
+}
+
+/// Time in raw samples (`u64`).
+#[derive(Clone, Copy, Debug)]
+pub struct Samples;
+
+impl SampleUnit for Samples {
+  type Time = u64;
+
+  fn from_samples(samples: u64, _: u32) -> Self::Time {
+    samples
+  }
+
+  fn to_samples(time: Self::Time, _: u32) -> i64 {
+    i64::try_from(time).unwrap_or(i64::MAX)
+  }
+}
+
+/// A [`TimeGenerator`] following an audio sample counter.
+///
+/// Time is driven by the [`SampleCounter`] only: ticking and unticking don’t move time, they just
+/// read the counter, and changing the delta has no effect. Setting the time shifts the generator
+/// relative to the counter (the audio stream is left untouched); resetting removes that shift, so
+/// that time follows the counter again. Time never goes below zero.
+pub struct SampleClockTimeGenerator<U = Seconds> {
+  counter: SampleCounter,
+  sample_rate: u32,
+  // shift, in samples, applied to the counter
+  offset: i64,
+  _unit: PhantomData<U>
+}
+
+impl SampleClockTimeGenerator {
+  /// Create a new [`SampleClockTimeGenerator`] following `counter`, generating time in seconds.
+  pub fn new(counter: SampleCounter, sample_rate: u32) -> Self {
+    Self::with_unit(counter, sample_rate)
+  }
+}
+
+impl<U> SampleClockTimeGenerator<U> where U: SampleUnit {
+  /// Create a new [`SampleClockTimeGenerator`] following `counter`, generating time in the unit
+  /// `U` ([`Seconds`] or [`Samples`]).
+  pub fn with_unit(counter: SampleCounter, sample_rate: u32) -> Self {
+    SampleClockTimeGenerator {
+      counter,
+      sample_rate,
+      offset: 0,
+      _unit: PhantomData
+    }
+  }
+
+  /// Counter followed by the generator.
+  pub fn counter(&self) -> &SampleCounter {
+    &self.counter
+  }
+
+  /// Sample rate, in Hz.
+  pub fn sample_rate(&self) -> u32 {
+    self.sample_rate
+  }
+
+  /// Current position, in samples.
+  pub fn samples(&self) -> u64 {
+    let samples = i64::try_from(self.counter.get()).unwrap_or(i64::MAX).saturating_add(self.offset);
+    samples.max(0) as u64
+  }
+
+  /// Current position, in seconds.
+  pub fn seconds(&self) -> f64 {
+    Seconds::from_samples(self.samples(), self.sample_rate)
+  }
+}
+
+impl<U> TimeGenerator for SampleClockTimeGenerator<U> where U: SampleUnit {
+  type Time = U::Time;
+
+  fn current(&self) -> Self::Time {
+    U::from_samples(self.samples(), self.sample_rate)
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.current()
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.current()
+  }
+
+  fn reset(&mut self) {
+    self.offset = 0;
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    let counter = i64::try_from(self.counter.get()).unwrap_or(i64::MAX);
+    self.offset = U::to_samples(value, self.sample_rate).saturating_sub(counter);
+  }
+
+  fn change_delta(&mut self, _: Self::Time) {}
+}
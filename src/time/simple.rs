@@ -1,23 +1,31 @@
-//! The simple floating, 32-bit time generator.
+//! The simple time generator.
 
-use crate::time::TimeGenerator;
+use crate::time::{Arithmetic, TimeGenerator};
 
-/// A simple [`TimeGenerator`] that generates `f32` times.
+/// A simple [`TimeGenerator`] that generates linear time.
 ///
 /// You can create one by giving it its initial state (typically `0.`) and a delta. For instance,
 /// for a video game that runs at 100 Hz, you want a delta set to 0.01, because you need to generate
 /// a frame every 10ms. If the framerate drops, you can change the value of the delta parameter to
 /// adapt.
-pub struct SimpleF32TimeGenerator {
-  current: f32,
-  reset_value: f32,
-  delta: f32
+///
+/// Any [`Arithmetic`] type can be used as time: floating-point numbers, integers (frame counters,
+/// for instance), [`Duration`], etc.
+///
+/// [`Duration`]: std::time::Duration
+pub struct SimpleTimeGenerator<T> {
+  current: T,
+  reset_value: T,
+  delta: T
 }
 
-impl SimpleF32TimeGenerator {
-  /// Create a new [`SimpleF32TimeGenerator`].
-  pub fn new(reset_value: f32, delta: f32) -> Self {
-    SimpleF32TimeGenerator {
+/// A simple [`TimeGenerator`] that generates `f32` times.
+pub type SimpleF32TimeGenerator = SimpleTimeGenerator<f32>;
+
+impl<T> SimpleTimeGenerator<T> where T: Arithmetic {
+  /// Create a new [`SimpleTimeGenerator`].
+  pub fn new(reset_value: T, delta: T) -> Self {
+    SimpleTimeGenerator {
       current: reset_value,
       reset_value,
       delta
@@ -25,8 +33,8 @@ impl SimpleF32TimeGenerator {
   }
 }
 
-impl TimeGenerator for SimpleF32TimeGenerator {
-  type Time = f32;
+impl<T> TimeGenerator for SimpleTimeGenerator<T> where T: Arithmetic {
+  type Time = T;
 
   fn current(&self) -> Self::Time {
     self.current
@@ -34,13 +42,13 @@ impl TimeGenerator for SimpleF32TimeGenerator {
 
   fn tick(&mut self) -> Self::Time {
     let t = self.current;
-    self.current += self.delta;
+    self.current = self.current.add(self.delta);
     t
   }
 
   fn untick(&mut self) -> Self::Time {
     let t = self.current;
-    self.current -= self.delta;
+    self.current = self.current.sub(self.delta);
     t
   }
 
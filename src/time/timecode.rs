@@ -0,0 +1,421 @@
+//! SMPTE timecode.
+//!
+//! A [`Timecode`] is a frame position in a video, written `HH:MM:SS:FF`. Drop-frame timecode (used
+//! at 29.97 frames per second) is written with a `;` before the frame field: `HH:MM:SS;FF`. Since
+//! the frame rate cannot be deduced from the regular form, [`Timecode`] also parses (and serializes
+//! to) a form with an explicit frame rate: `01:00:00:00@25`.
+//!
+//! Timecodes can be scheduled frame by frame with a [`TimecodeTimeGenerator`]:
+//!
+//! ```
+//! use awoo::time::TimeGenerator;
+//! use awoo::time::timecode::{FrameRate, Timecode, TimecodeTimeGenerator};
+//!
+//! let start: Timecode = "00:00:59;29".parse().unwrap();
+//! let mut gen = TimecodeTimeGenerator::frame_by_frame(start);
+//!
+//! gen.tick();
+//! assert_eq!(gen.current().to_string(), "00:01:00;02");
+//! ```
+//!
+//! [`Timecode`]: crate::time::timecode::Timecode
+//! [`TimecodeTimeGenerator`]: crate::time::timecode::TimecodeTimeGenerator
+
+#[cfg(feature = "serde")] use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::time::simple::SimpleTimeGenerator;
+use crate::time::{Arithmetic, Normalize, Scalable, TimeOrd};
+
+/// Frame rates supported by [`Timecode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FrameRate {
+  /// 24 frames per second (film).
+  Fps24,
+  /// 25 frames per second (PAL).
+  Fps25,
+  /// 29.97 frames per second, drop-frame (NTSC).
+  Fps29_97DropFrame,
+  /// 30 frames per second.
+  Fps30
+}
+
+impl FrameRate {
+  /// Frames per second, as a fraction `(numerator, denominator)`.
+  pub fn fps(self) -> (u64, u64) {
+    match self {
+      FrameRate::Fps24 => (24, 1),
+      FrameRate::Fps25 => (25, 1),
+      FrameRate::Fps29_97DropFrame => (30000, 1001),
+      FrameRate::Fps30 => (30, 1)
+    }
+  }
+
+  /// Number of frames per second in timecode labels.
+  ///
+  /// For drop-frame rates, that is the rounded frame rate.
+  pub fn nominal_fps(self) -> u64 {
+    match self {
+      FrameRate::Fps24 => 24,
+      FrameRate::Fps25 => 25,
+      FrameRate::Fps29_97DropFrame | FrameRate::Fps30 => 30
+    }
+  }
+
+  /// Whether the frame rate uses drop-frame timecode.
+  pub fn is_drop_frame(self) -> bool {
+    self == FrameRate::Fps29_97DropFrame
+  }
+}
+
+impl fmt::Display for FrameRate {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      FrameRate::Fps24 => f.write_str("24"),
+      FrameRate::Fps25 => f.write_str("25"),
+      FrameRate::Fps29_97DropFrame => f.write_str("29.97"),
+      FrameRate::Fps30 => f.write_str("30")
+    }
+  }
+}
+
+impl FromStr for FrameRate {
+  type Err = TimecodeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "24" => Ok(FrameRate::Fps24),
+      "25" => Ok(FrameRate::Fps25),
+      "29.97" => Ok(FrameRate::Fps29_97DropFrame),
+      "30" => Ok(FrameRate::Fps30),
+      _ => Err(TimecodeError::UnknownFrameRate(s.to_owned()))
+    }
+  }
+}
+
+// drop-frame constants: two frame labels are dropped every minute, except every tenth minute
+const DF_FRAMES_PER_MINUTE: u64 = 60 * 30 - 2;
+const DF_FRAMES_PER_10_MINUTES: u64 = 10 * DF_FRAMES_PER_MINUTE + 2;
+
+/// An SMPTE timecode.
+///
+/// A timecode is a number of frames since `00:00:00:00` at a given frame rate. Timecodes at
+/// different frame rates can be compared (they are compared by the time they represent), but
+/// arithmetic keeps the frame rate of the left operand, converting the right one to the closest
+/// frame. The zero timecode ([`Arithmetic::zero`]) adopts the frame rate of the other operand.
+///
+/// [`Arithmetic::zero`]: crate::time::Arithmetic::zero
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Timecode {
+  frame: u64,
+  rate: FrameRate
+}
+
+impl Timecode {
+  /// Create a timecode from a number of frames.
+  pub fn from_frames(frame: u64, rate: FrameRate) -> Self {
+    Timecode {
+      frame,
+      rate
+    }
+  }
+
+  /// Create a timecode from its components.
+  ///
+  /// Fail if a component is out of range (including hours too large to be represented) or if the
+  /// timecode is dropped (drop-frame only).
+  pub fn new(
+    hours: u64,
+    minutes: u64,
+    seconds: u64,
+    frames: u64,
+    rate: FrameRate
+  ) -> Result<Self, TimecodeError> {
+    let fps = rate.nominal_fps();
+
+    if minutes >= 60 || seconds >= 60 || frames >= fps {
+      return Err(TimecodeError::OutOfRange);
+    }
+
+    let total_minutes = hours
+      .checked_mul(60)
+      .and_then(|m| m.checked_add(minutes))
+      .ok_or(TimecodeError::OutOfRange)?;
+    let mut frame = total_minutes
+      .checked_mul(60)
+      .and_then(|s| s.checked_add(seconds))
+      .and_then(|s| s.checked_mul(fps))
+      .and_then(|f| f.checked_add(frames))
+      .ok_or(TimecodeError::OutOfRange)?;
+
+    if rate.is_drop_frame() {
+      if seconds == 0 && frames < 2 && !minutes.is_multiple_of(10) {
+        return Err(TimecodeError::DroppedFrame);
+      }
+
+      frame -= 2 * (total_minutes - total_minutes / 10);
+    }
+
+    Ok(Timecode { frame, rate })
+  }
+
+  /// Parse a timecode in its standard form (`HH:MM:SS:FF` or `HH:MM:SS;FF`) at a given frame rate.
+  pub fn parse(s: &str, rate: FrameRate) -> Result<Self, TimecodeError> {
+    let malformed = || TimecodeError::Malformed(s.to_owned());
+    let (hms, frames) = s.rsplit_once([':', ';']).ok_or_else(malformed)?;
+
+    if s[hms.len() ..].starts_with(';') != rate.is_drop_frame() {
+      return Err(malformed());
+    }
+
+    let mut fields = hms.split(':').map(|field| {
+      if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+        Err(malformed())
+      } else {
+        field.parse::<u64>().map_err(|_| malformed())
+      }
+    });
+
+    let hours = fields.next().ok_or_else(malformed)??;
+    let minutes = fields.next().ok_or_else(malformed)??;
+    let seconds = fields.next().ok_or_else(malformed)??;
+
+    if fields.next().is_some() || frames.is_empty() || !frames.bytes().all(|b| b.is_ascii_digit()) {
+      return Err(malformed());
+    }
+
+    let frames = frames.parse().map_err(|_| malformed())?;
+    Timecode::new(hours, minutes, seconds, frames, rate)
+  }
+
+  /// Number of frames since `00:00:00:00`.
+  pub fn frames(&self) -> u64 {
+    self.frame
+  }
+
+  /// Frame rate.
+  pub fn rate(&self) -> FrameRate {
+    self.rate
+  }
+
+  /// Time represented by the timecode, in seconds.
+  pub fn seconds(&self) -> f64 {
+    let (num, den) = self.rate.fps();
+    self.frame as f64 * den as f64 / num as f64
+  }
+
+  /// Components of the timecode: `(hours, minutes, seconds, frames)`.
+  pub fn components(&self) -> (u64, u64, u64, u64) {
+    let fps = self.rate.nominal_fps();
+    let mut label = self.frame;
+
+    if self.rate.is_drop_frame() {
+      let tens = self.frame / DF_FRAMES_PER_10_MINUTES;
+      let rem = self.frame % DF_FRAMES_PER_10_MINUTES;
+
+      label += 18 * tens;
+
+      if rem >= 2 {
+        label += 2 * ((rem - 2) / DF_FRAMES_PER_MINUTE);
+      }
+    }
+
+    (label / (fps * 3600), label / (fps * 60) % 60, label / fps % 60, label % fps)
+  }
+
+  /// Convert the timecode to another frame rate, rounding to the closest frame.
+  pub fn to_rate(&self, rate: FrameRate) -> Self {
+    if rate == self.rate {
+      return *self;
+    }
+
+    let (num, den) = self.rate.fps();
+    let (to_num, to_den) = rate.fps();
+    let n = u128::from(self.frame) * u128::from(den) * u128::from(to_num);
+    let d = u128::from(num) * u128::from(to_den);
+
+    Timecode::from_frames(((n + d / 2) / d) as u64, rate)
+  }
+
+  // bring rhs to our frame rate, unless we are zero, in which case we adopt the one of rhs
+  fn common_rate(self, rhs: Self) -> (Self, Self) {
+    if self.frame == 0 {
+      (Timecode::from_frames(0, rhs.rate), rhs)
+    } else {
+      (self, rhs.to_rate(self.rate))
+    }
+  }
+}
+
+impl PartialEq for Timecode {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Timecode {}
+
+impl PartialOrd for Timecode {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Timecode {
+  fn cmp(&self, other: &Self) -> Ordering {
+    if self.rate == other.rate {
+      return self.frame.cmp(&other.frame);
+    }
+
+    let (num, den) = self.rate.fps();
+    let (other_num, other_den) = other.rate.fps();
+    let a = u128::from(self.frame) * u128::from(den) * u128::from(other_num);
+    let b = u128::from(other.frame) * u128::from(other_den) * u128::from(num);
+
+    a.cmp(&b)
+  }
+}
+
+impl TimeOrd for Timecode {
+  fn time_cmp(&self, other: &Self) -> Ordering {
+    self.cmp(other)
+  }
+}
+
+impl Add for Timecode {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self {
+    let (a, b) = self.common_rate(rhs);
+    Timecode::from_frames(a.frame.saturating_add(b.frame), a.rate)
+  }
+}
+
+impl Sub for Timecode {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self {
+    let (a, b) = self.common_rate(rhs);
+    Timecode::from_frames(a.frame.saturating_sub(b.frame), a.rate)
+  }
+}
+
+impl Arithmetic for Timecode {
+  fn zero() -> Self {
+    Timecode::from_frames(0, FrameRate::Fps24)
+  }
+
+  fn add(self, rhs: Self) -> Self {
+    self + rhs
+  }
+
+  fn sub(self, rhs: Self) -> Self {
+    self - rhs
+  }
+
+  fn times(self, n: u64) -> Self {
+    Timecode::from_frames(self.frame.saturating_mul(n), self.rate)
+  }
+
+  fn rem(self, rhs: Self) -> Self {
+    let (a, b) = self.common_rate(rhs);
+    Timecode::from_frames(a.frame % b.frame, a.rate)
+  }
+}
+
+impl Normalize for Timecode {
+  fn normalize(self, length: Self) -> f32 {
+    (self.seconds() / length.seconds()) as f32
+  }
+}
+
+impl Scalable for Timecode {
+  fn scale(self, factor: f64) -> Self {
+    Timecode::from_frames((self.frame as f64 * factor).round() as u64, self.rate)
+  }
+}
+
+impl fmt::Display for Timecode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let (hours, minutes, seconds, frames) = self.components();
+    let sep = if self.rate.is_drop_frame() { ';' } else { ':' };
+
+    write!(f, "{:02}:{:02}:{:02}{}{:02}", hours, minutes, seconds, sep, frames)
+  }
+}
+
+impl FromStr for Timecode {
+  type Err = TimecodeError;
+
+  /// Parse a timecode with an explicit frame rate (`01:00:00:00@25`) or a drop-frame timecode
+  /// (`01:00:00;00`), which is assumed to be at 29.97 frames per second.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.split_once('@') {
+      Some((tc, rate)) => Timecode::parse(tc, rate.parse()?),
+      None if s.contains(';') => Timecode::parse(s, FrameRate::Fps29_97DropFrame),
+      None => Err(TimecodeError::MissingFrameRate(s.to_owned()))
+    }
+  }
+}
+
+impl TryFrom<String> for Timecode {
+  type Error = TimecodeError;
+
+  fn try_from(s: String) -> Result<Self, Self::Error> {
+    s.parse()
+  }
+}
+
+impl From<Timecode> for String {
+  fn from(tc: Timecode) -> Self {
+    format!("{}@{}", tc, tc.rate)
+  }
+}
+
+/// Errors that might occur when creating or parsing a [`Timecode`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TimecodeError {
+  /// The timecode is not of the form `HH:MM:SS:FF` or `HH:MM:SS;FF` (drop-frame).
+  Malformed(String),
+  /// A component of the timecode is out of range.
+  OutOfRange,
+  /// The timecode doesn’t exist because it’s dropped (drop-frame only).
+  DroppedFrame,
+  /// The frame rate is not supported.
+  UnknownFrameRate(String),
+  /// The frame rate cannot be deduced from the timecode.
+  MissingFrameRate(String)
+}
+
+impl fmt::Display for TimecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      TimecodeError::Malformed(ref s) => write!(f, "malformed timecode: {}", s),
+      TimecodeError::OutOfRange => f.write_str("timecode component out of range"),
+      TimecodeError::DroppedFrame => f.write_str("dropped timecode"),
+      TimecodeError::UnknownFrameRate(ref s) => write!(f, "unknown frame rate: {}", s),
+      TimecodeError::MissingFrameRate(ref s) => write!(f, "missing frame rate in timecode: {}", s)
+    }
+  }
+}
+
+impl Error for TimecodeError {}
+
+/// A [`TimeGenerator`] generating timecodes.
+///
+/// [`TimeGenerator`]: crate::time::TimeGenerator
+pub type TimecodeTimeGenerator = SimpleTimeGenerator<Timecode>;
+
+impl SimpleTimeGenerator<Timecode> {
+  /// Create a [`TimecodeTimeGenerator`] starting at a given timecode and ticking frame by frame.
+  pub fn frame_by_frame(start: Timecode) -> Self {
+    SimpleTimeGenerator::new(start, Timecode::from_frames(1, start.rate))
+  }
+}
@@ -0,0 +1,191 @@
+//! The wall-clock time generator.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use crate::time::TimeGenerator;
+
+/// Source of monotonic time.
+///
+/// A clock gives the time elapsed since an arbitrary, fixed point in time. [`SystemClock`] is the
+/// clock to use for realtime playback; [`ManualClock`] is a clock that only moves when told to,
+/// which is useful to test time-dependent code deterministically.
+pub trait Clock {
+  /// Time elapsed since the origin of the clock.
+  fn now(&self) -> Duration;
+}
+
+impl<C> Clock for &C where C: Clock {
+  fn now(&self) -> Duration {
+    (**self).now()
+  }
+}
+
+/// The system’s monotonic clock, backed by [`Instant`].
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+  origin: Instant
+}
+
+impl SystemClock {
+  /// Create a new system clock, which origin is now.
+  pub fn new() -> Self {
+    SystemClock {
+      origin: Instant::now()
+    }
+  }
+}
+
+impl Default for SystemClock {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Clock for SystemClock {
+  fn now(&self) -> Duration {
+    self.origin.elapsed()
+  }
+}
+
+/// A clock that only moves when told to.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+  now: Cell<Duration>
+}
+
+impl ManualClock {
+  /// Create a new manual clock, starting at zero.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Move the clock forward.
+  pub fn advance(&self, by: Duration) {
+    self.now.set(self.now.get() + by);
+  }
+}
+
+impl Clock for ManualClock {
+  fn now(&self) -> Duration {
+    self.now.get()
+  }
+}
+
+/// A [`TimeGenerator`] following a real clock.
+///
+/// Time is expressed in seconds. Instead of moving by a fixed delta, time moves by the amount of
+/// time elapsed on the [`Clock`] since the last tick, multiplied by the playback speed. Unticking
+/// moves time by the same amount, but backwards. The playback speed is the _delta_ of that time
+/// generator: changing the delta changes the speed. A negative speed plays time backwards.
+///
+/// The generator can also be paused: while paused, ticking doesn’t move time.
+pub struct WallClockTimeGenerator<C = SystemClock> {
+  clock: C,
+  current: f64,
+  reset_value: f64,
+  last_sample: Duration,
+  speed: f64,
+  paused: bool
+}
+
+impl WallClockTimeGenerator {
+  /// Create a new [`WallClockTimeGenerator`] following the system’s clock, starting at zero.
+  pub fn new() -> Self {
+    Self::with_clock(SystemClock::new(), 0.)
+  }
+}
+
+impl Default for WallClockTimeGenerator {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<C> WallClockTimeGenerator<C> where C: Clock {
+  /// Create a new [`WallClockTimeGenerator`] following the given clock.
+  pub fn with_clock(clock: C, reset_value: f64) -> Self {
+    let last_sample = clock.now();
+
+    WallClockTimeGenerator {
+      clock,
+      current: reset_value,
+      reset_value,
+      last_sample,
+      speed: 1.,
+      paused: false
+    }
+  }
+
+  /// Pause time.
+  pub fn pause(&mut self) {
+    self.paused = true;
+  }
+
+  /// Resume time.
+  ///
+  /// Time elapsed while paused is not taken into account.
+  pub fn resume(&mut self) {
+    self.paused = false;
+    self.last_sample = self.clock.now();
+  }
+
+  /// Whether time is paused.
+  pub fn is_paused(&self) -> bool {
+    self.paused
+  }
+
+  /// Playback speed.
+  pub fn speed(&self) -> f64 {
+    self.speed
+  }
+
+  /// Change the playback speed.
+  pub fn set_speed(&mut self, speed: f64) {
+    self.speed = speed;
+  }
+
+  // move time by the time elapsed since the last sample, in the given direction
+  fn advance(&mut self, direction: f64) -> f64 {
+    let t = self.current;
+    let now = self.clock.now();
+    let elapsed = now.saturating_sub(self.last_sample);
+
+    self.last_sample = now;
+
+    if !self.paused {
+      self.current += elapsed.as_secs_f64() * self.speed * direction;
+    }
+
+    t
+  }
+}
+
+impl<C> TimeGenerator for WallClockTimeGenerator<C> where C: Clock {
+  type Time = f64;
+
+  fn current(&self) -> Self::Time {
+    self.current
+  }
+
+  fn tick(&mut self) -> Self::Time {
+    self.advance(1.)
+  }
+
+  fn untick(&mut self) -> Self::Time {
+    self.advance(-1.)
+  }
+
+  fn reset(&mut self) {
+    self.set(self.reset_value);
+  }
+
+  fn set(&mut self, value: Self::Time) {
+    self.current = value;
+    self.last_sample = self.clock.now();
+  }
+
+  fn change_delta(&mut self, delta: Self::Time) {
+    self.set_speed(delta);
+  }
+}
@@ -0,0 +1,521 @@
+//! Declarative timelines.
+//!
+//! A [`Timeline`] is a document listing named windows, typically authored in a file rather than in
+//! code. Names refer to actions registered in an [`ActionRegistry`], which binds them to closures
+//! and builds schedulers out of timelines:
+//!
+//! ```
+//! use awoo::time::simple::SimpleF32TimeGenerator;
+//! use awoo::timeline::{ActionRegistry, Timeline};
+//!
+//! let timeline: Timeline<f32> = serde_json::from_str(r#"{
+//!   "windows": [
+//!     { "name": "intro", "start": 0, "end": 3 },
+//!     { "name": "scene", "start": 3, "end": 10, "metadata": { "camera": "orbit" } },
+//!     { "name": "intro", "start": 10, "end": 12 }
+//!   ]
+//! }"#).unwrap();
+//!
+//! let mut registry = ActionRegistry::new();
+//! registry
+//!   .register("intro", |t| println!("intro: {}", t))
+//!   .register("scene", |t| println!("scene: {}", t));
+//!
+//! let mut scheduler = registry.random_access_scheduler(SimpleF32TimeGenerator::new(0., 1.), &timeline).unwrap();
+//! scheduler.schedule();
+//! ```
+//!
+//! Several windows can share the same action. Every window of the timeline must be bound to an
+//! action, and every registered action must be used by the timeline: both mistakes are reported as
+//! [`TimelineError::Binding`].
+//!
+//! Timeline files can be written in JSON, [RON] or TOML (see [`Format`]), each format being enabled by
+//! the feature of the same name. [`Timeline::load`] and [`Timeline::save`] pick the format from the
+//! file extension.
+//!
+//! [RON]: https://github.com/ron-rs/ron
+//! [`Format`]: crate::timeline::Format
+//! [`Timeline::load`]: crate::timeline::Timeline::load
+//! [`Timeline::save`]: crate::timeline::Timeline::save
+//! [`Timeline`]: crate::timeline::Timeline
+//! [`ActionRegistry`]: crate::timeline::ActionRegistry
+//! [`TimelineError::Binding`]: crate::timeline::TimelineError::Binding
+
+pub mod reload;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::easing::Easing;
+use crate::scheduler::layered::LayeredScheduler;
+use crate::scheduler::{RandomAccessScheduler, SchedulerError, SequentialScheduler};
+use crate::time::{Normalize, TimeGenerator};
+use crate::window::{LocalTime, MappedWindow, Window};
+
+/// A timeline document: a list of named windows.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timeline<T> {
+  /// Windows of the timeline.
+  pub windows: Vec<TimelineWindow<T>>
+}
+
+impl<T> Timeline<T> {
+  /// Create an empty timeline.
+  pub fn new() -> Self {
+    Timeline { windows: Vec::new() }
+  }
+
+  /// Add a window bound to the action `name`.
+  pub fn push<N>(&mut self, name: N, window: Window<T>) -> &mut TimelineWindow<T> where N: Into<String> {
+    self.windows.push(TimelineWindow::new(name, window));
+    self.windows.last_mut().unwrap()
+  }
+}
+
+impl<T> Timeline<T> where T: DeserializeOwned {
+  /// Parse a timeline in the given format.
+  pub fn parse(s: &str, format: Format) -> Result<Self, TimelineFileError> {
+    match format {
+      #[cfg(feature = "json")]
+      Format::Json => serde_json::from_str(s).map_err(|err| TimelineFileError::deserialize(format, err)),
+
+      #[cfg(feature = "ron")]
+      Format::Ron => ron::from_str(s).map_err(|err| TimelineFileError::deserialize(format, err)),
+
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::from_str(s).map_err(|err| TimelineFileError::deserialize(format, err)),
+
+      #[allow(unreachable_patterns)]
+      _ => Err(TimelineFileError::UnsupportedFormat(format))
+    }
+  }
+
+  /// Load a timeline file, which format is deduced from its extension (see [`Format::from_path`]).
+  pub fn load<P>(path: P) -> Result<Self, TimelineFileError> where P: AsRef<Path> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or_else(|| TimelineFileError::UnknownFormat(path.to_owned()))?;
+
+    Self::load_as(path, format)
+  }
+
+  /// Load a timeline file in the given format.
+  pub fn load_as<P>(path: P, format: Format) -> Result<Self, TimelineFileError> where P: AsRef<Path> {
+    Self::parse(&fs::read_to_string(path)?, format)
+  }
+
+  /// Parse a JSON timeline.
+  #[cfg(feature = "json")]
+  pub fn from_json(s: &str) -> Result<Self, TimelineFileError> {
+    Self::parse(s, Format::Json)
+  }
+
+  /// Load a JSON timeline file.
+  #[cfg(feature = "json")]
+  pub fn load_json<P>(path: P) -> Result<Self, TimelineFileError> where P: AsRef<Path> {
+    Self::load_as(path, Format::Json)
+  }
+}
+
+impl<T> Timeline<T> where T: Serialize {
+  /// Write the timeline in the given format.
+  pub fn to_string_as(&self, format: Format) -> Result<String, TimelineFileError> {
+    match format {
+      #[cfg(feature = "json")]
+      Format::Json => serde_json::to_string_pretty(self).map_err(|err| TimelineFileError::serialize(format, err)),
+
+      #[cfg(feature = "ron")]
+      Format::Ron => {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+          .map_err(|err| TimelineFileError::serialize(format, err))
+      }
+
+      #[cfg(feature = "toml")]
+      Format::Toml => toml::to_string_pretty(self).map_err(|err| TimelineFileError::serialize(format, err)),
+
+      #[allow(unreachable_patterns)]
+      _ => Err(TimelineFileError::UnsupportedFormat(format))
+    }
+  }
+
+  /// Save the timeline to a file, which format is deduced from its extension (see
+  /// [`Format::from_path`]).
+  pub fn save<P>(&self, path: P) -> Result<(), TimelineFileError> where P: AsRef<Path> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or_else(|| TimelineFileError::UnknownFormat(path.to_owned()))?;
+
+    self.save_as(path, format)
+  }
+
+  /// Save the timeline to a file in the given format.
+  pub fn save_as<P>(&self, path: P, format: Format) -> Result<(), TimelineFileError> where P: AsRef<Path> {
+    fs::write(path, self.to_string_as(format)?)?;
+    Ok(())
+  }
+}
+
+/// Timeline file formats.
+///
+/// Each format requires its own feature: `json`, `ron` or `toml`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+  /// JSON.
+  Json,
+  /// Rusty Object Notation.
+  Ron,
+  /// TOML.
+  Toml
+}
+
+impl Format {
+  /// Deduce the format of a file from its extension (`.json`, `.ron` or `.toml`).
+  pub fn from_path<P>(path: P) -> Option<Self> where P: AsRef<Path> {
+    let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+
+    match ext.as_str() {
+      "json" => Some(Format::Json),
+      "ron" => Some(Format::Ron),
+      "toml" => Some(Format::Toml),
+      _ => None
+    }
+  }
+}
+
+impl fmt::Display for Format {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Format::Json => f.write_str("JSON"),
+      Format::Ron => f.write_str("RON"),
+      Format::Toml => f.write_str("TOML")
+    }
+  }
+}
+
+/// A named window of a [`Timeline`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimelineWindow<T> {
+  /// Name of the action to perform inside the window.
+  pub name: String,
+  /// Start time (inclusive) of the window.
+  pub start: T,
+  /// End time (exclusive) of the window.
+  pub end: T,
+  /// Layer of the window (see [`MappedWindow::with_layer`]).
+  ///
+  /// [`MappedWindow::with_layer`]: crate::window::MappedWindow::with_layer
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub layer: i32,
+  /// Easing of the progress given to actions registered with [`ActionRegistry::register_local`].
+  ///
+  /// Actions registered with [`ActionRegistry::register`] only receive the absolute time, so the
+  /// easing is ignored for them.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub easing: Option<Easing>,
+  /// Free-form metadata, ignored by `awoo`.
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub metadata: BTreeMap<String, String>
+}
+
+fn is_zero(layer: &i32) -> bool {
+  *layer == 0
+}
+
+impl<T> TimelineWindow<T> {
+  /// Create a new named window, on layer `0`, without easing nor metadata.
+  pub fn new<N>(name: N, window: Window<T>) -> Self where N: Into<String> {
+    TimelineWindow {
+      name: name.into(),
+      start: window.start,
+      end: window.end,
+      layer: 0,
+      easing: None,
+      metadata: BTreeMap::new()
+    }
+  }
+
+  /// Time window.
+  pub fn window(&self) -> Window<T> where T: Copy {
+    Window::new(self.start, self.end)
+  }
+
+  /// Set the layer of the window.
+  pub fn with_layer(&mut self, layer: i32) -> &mut Self {
+    self.layer = layer;
+    self
+  }
+
+  /// Set the easing of the window.
+  pub fn with_easing(&mut self, easing: Easing) -> &mut Self {
+    self.easing = Some(easing);
+    self
+  }
+
+  /// Add metadata to the window.
+  pub fn with_metadata<K, V>(&mut self, key: K, value: V) -> &mut Self where K: Into<String>, V: Into<String> {
+    self.metadata.insert(key.into(), value.into());
+    self
+  }
+}
+
+// binds a timeline window to a registered action, shared by all the windows using its name
+type Binder<'a, T> = Box<dyn Fn(&TimelineWindow<T>) -> MappedWindow<'a, T> + 'a>;
+
+/// Named actions, bound to the windows of [`Timeline`]s.
+pub struct ActionRegistry<'a, T> {
+  actions: HashMap<String, Binder<'a, T>>
+}
+
+impl<'a, T> Default for ActionRegistry<'a, T> {
+  fn default() -> Self {
+    ActionRegistry {
+      actions: HashMap::new()
+    }
+  }
+}
+
+impl<'a, T> ActionRegistry<'a, T> where T: Copy + 'a {
+  /// Create an empty registry.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Register an action under a name.
+  ///
+  /// If an action was already registered under that name, it’s replaced.
+  pub fn register<N, F>(&mut self, name: N, f: F) -> &mut Self where N: Into<String>, F: FnMut(T) + 'a {
+    let action = Rc::new(RefCell::new(f));
+    let bind = move |tw: &TimelineWindow<T>| {
+      let action = action.clone();
+      tw.window().map(move |t| (action.borrow_mut())(t))
+    };
+
+    self.actions.insert(name.into(), Box::new(bind));
+    self
+  }
+
+  /// Whether an action is registered under a name.
+  pub fn is_registered(&self, name: &str) -> bool {
+    self.actions.contains_key(name)
+  }
+
+  /// Bind the windows of a timeline to the registered actions.
+  ///
+  /// Windows are returned in the order of the timeline, so that indices in [`SchedulerError`]s
+  /// refer to the windows of the timeline.
+  pub fn windows(&self, timeline: &Timeline<T>) -> Result<Vec<MappedWindow<'a, T>>, TimelineError<T>> {
+    let mut unbound = Vec::new();
+    let mut windows = Vec::with_capacity(timeline.windows.len());
+
+    for tw in &timeline.windows {
+      match self.actions.get(&tw.name) {
+        Some(bind) => windows.push(bind(tw).with_layer(tw.layer)),
+
+        None => {
+          if !unbound.contains(&tw.name) {
+            unbound.push(tw.name.clone());
+          }
+        }
+      }
+    }
+
+    let mut unused: Vec<String> = self
+      .actions
+      .keys()
+      .filter(|name| timeline.windows.iter().all(|tw| &tw.name != *name))
+      .cloned()
+      .collect();
+    unused.sort();
+
+    if unbound.is_empty() && unused.is_empty() {
+      Ok(windows)
+    } else {
+      Err(TimelineError::Binding { unbound, unused })
+    }
+  }
+
+  /// Build a [`RandomAccessScheduler`] out of a timeline.
+  pub fn random_access_scheduler<G>(
+    &self,
+    time_gen: G,
+    timeline: &Timeline<T>
+  ) -> Result<RandomAccessScheduler<'a, G>, TimelineError<T>>
+  where G: TimeGenerator<Time = T> {
+    Ok(RandomAccessScheduler::new(time_gen, self.windows(timeline)?)?)
+  }
+
+  /// Build a [`SequentialScheduler`] out of a timeline.
+  pub fn sequential_scheduler<G>(
+    &self,
+    time_gen: G,
+    timeline: &Timeline<T>
+  ) -> Result<SequentialScheduler<'a, G>, TimelineError<T>>
+  where G: TimeGenerator<Time = T> {
+    Ok(SequentialScheduler::new(time_gen, self.windows(timeline)?)?)
+  }
+
+  /// Build a [`LayeredScheduler`] out of a timeline.
+  pub fn layered_scheduler<G>(
+    &self,
+    time_gen: G,
+    timeline: &Timeline<T>
+  ) -> Result<LayeredScheduler<'a, G>, TimelineError<T>>
+  where G: TimeGenerator<Time = T> {
+    Ok(LayeredScheduler::new(time_gen, self.windows(timeline)?)?)
+  }
+}
+
+impl<'a, T> ActionRegistry<'a, T> where T: Copy + Normalize + 'a {
+  /// Register an action receiving window-local time under a name.
+  ///
+  /// The progress of the [`LocalTime`] given to the action is eased with the [`easing`] of the
+  /// window, if any. If an action was already registered under that name, it’s replaced.
+  ///
+  /// [`easing`]: TimelineWindow::easing
+  pub fn register_local<N, F>(&mut self, name: N, f: F) -> &mut Self
+  where N: Into<String>, F: FnMut(LocalTime<T>) + 'a {
+    let action = Rc::new(RefCell::new(f));
+    let bind = move |tw: &TimelineWindow<T>| {
+      let action = action.clone();
+      let easing = tw.easing.unwrap_or_default();
+
+      tw.window().map_local(move |mut local| {
+        local.progress = local.eased(easing);
+        (action.borrow_mut())(local)
+      })
+    };
+
+    self.actions.insert(name.into(), Box::new(bind));
+    self
+  }
+}
+
+/// Errors that might occur when building schedulers out of timelines.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimelineError<T> {
+  /// Some windows are not bound to registered actions, or some registered actions are not used.
+  Binding {
+    /// Names used by windows but not registered.
+    unbound: Vec<String>,
+    /// Names registered but not used by any window.
+    unused: Vec<String>
+  },
+  /// The windows are invalid.
+  Scheduler(SchedulerError<T>)
+}
+
+impl<T> From<SchedulerError<T>> for TimelineError<T> {
+  fn from(err: SchedulerError<T>) -> Self {
+    TimelineError::Scheduler(err)
+  }
+}
+
+impl<T> fmt::Display for TimelineError<T> where T: fmt::Debug {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      TimelineError::Binding { ref unbound, ref unused } => {
+        f.write_str("invalid timeline bindings")?;
+
+        if !unbound.is_empty() {
+          write!(f, "; unbound actions: {}", unbound.join(", "))?;
+        }
+
+        if !unused.is_empty() {
+          write!(f, "; unused actions: {}", unused.join(", "))?;
+        }
+
+        Ok(())
+      }
+
+      TimelineError::Scheduler(ref err) => write!(f, "invalid timeline windows: {}", err)
+    }
+  }
+}
+
+impl<T> Error for TimelineError<T> where T: fmt::Debug + 'static {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      TimelineError::Scheduler(ref err) => Some(err),
+      _ => None
+    }
+  }
+}
+
+/// Errors that might occur when reading or writing timeline files.
+#[derive(Debug)]
+pub enum TimelineFileError {
+  /// The file couldn’t be read or written.
+  Io(io::Error),
+  /// The format of the file couldn’t be deduced from its extension.
+  UnknownFormat(PathBuf),
+  /// The feature required by the format is disabled.
+  UnsupportedFormat(Format),
+  /// The document is not a valid timeline.
+  Deserialize {
+    /// Format of the document.
+    format: Format,
+    /// Error reported by the format.
+    source: Box<dyn Error + Send + Sync>
+  },
+  /// The timeline cannot be represented in the format.
+  Serialize {
+    /// Format of the document.
+    format: Format,
+    /// Error reported by the format.
+    source: Box<dyn Error + Send + Sync>
+  }
+}
+
+impl TimelineFileError {
+  #[allow(dead_code)]
+  fn deserialize<E>(format: Format, err: E) -> Self where E: Error + Send + Sync + 'static {
+    TimelineFileError::Deserialize {
+      format,
+      source: Box::new(err)
+    }
+  }
+
+  #[allow(dead_code)]
+  fn serialize<E>(format: Format, err: E) -> Self where E: Error + Send + Sync + 'static {
+    TimelineFileError::Serialize {
+      format,
+      source: Box::new(err)
+    }
+  }
+}
+
+impl From<io::Error> for TimelineFileError {
+  fn from(err: io::Error) -> Self {
+    TimelineFileError::Io(err)
+  }
+}
+
+impl fmt::Display for TimelineFileError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      TimelineFileError::Io(ref err) => write!(f, "cannot access timeline file: {}", err),
+      TimelineFileError::UnknownFormat(ref path) => write!(f, "unknown timeline format: {}", path.display()),
+      TimelineFileError::UnsupportedFormat(format) => write!(f, "{} timelines are not supported (feature disabled)", format),
+      TimelineFileError::Deserialize { format, ref source } => write!(f, "invalid {} timeline: {}", format, source),
+      TimelineFileError::Serialize { format, ref source } => write!(f, "cannot write {} timeline: {}", format, source)
+    }
+  }
+}
+
+impl Error for TimelineFileError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      TimelineFileError::Io(ref err) => Some(err),
+      TimelineFileError::Deserialize { ref source, .. } | TimelineFileError::Serialize { ref source, .. } => {
+        Some(source.as_ref())
+      }
+      _ => None
+    }
+  }
+}
@@ -0,0 +1,198 @@
+//! Hot-reloading of timeline files.
+//!
+//! A [`TimelineWatcher`] polls the modification time of a timeline file. When the file changes, it
+//! reloads it, binds it to the actions of an [`ActionRegistry`] and swaps the windows of a
+//! scheduler in place, keeping its time generator (and thus the current time) untouched. If the new
+//! timeline is invalid, the error is reported and the scheduler keeps running the previous windows:
+//!
+//! ```no_run
+//! use awoo::scheduler::RandomAccessScheduler;
+//! use awoo::time::simple::SimpleF32TimeGenerator;
+//! use awoo::timeline::reload::TimelineWatcher;
+//! use awoo::timeline::{ActionRegistry, Timeline};
+//!
+//! let mut registry = ActionRegistry::new();
+//! registry.register("scene", |t| println!("scene: {}", t));
+//!
+//! let timeline = Timeline::load("timeline.json").unwrap();
+//! let mut scheduler = registry.random_access_scheduler(SimpleF32TimeGenerator::new(0., 0.01), &timeline).unwrap();
+//! let mut watcher = TimelineWatcher::new("timeline.json");
+//!
+//! loop {
+//!   if let Some(Err(err)) = watcher.reload(&registry, &mut scheduler) {
+//!     eprintln!("cannot reload timeline: {}", err);
+//!   }
+//!
+//!   scheduler.step();
+//! }
+//! ```
+//!
+//! [`TimelineWatcher`]: crate::timeline::reload::TimelineWatcher
+//! [`ActionRegistry`]: crate::timeline::ActionRegistry
+
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::scheduler::layered::LayeredScheduler;
+use crate::scheduler::{RandomAccessScheduler, SchedulerError, SequentialScheduler};
+use crate::time::TimeGenerator;
+use crate::timeline::{ActionRegistry, Timeline, TimelineError, TimelineFileError};
+use crate::window::MappedWindow;
+
+/// Schedulers which windows can be replaced during playback.
+pub trait ReplaceWindows<'a, T> {
+  /// Replace the mapped windows, leaving the time generator untouched.
+  fn replace_windows(&mut self, windows: Vec<MappedWindow<'a, T>>) -> Result<(), SchedulerError<T>>;
+}
+
+impl<'a, G> ReplaceWindows<'a, G::Time> for RandomAccessScheduler<'a, G> where G: TimeGenerator {
+  fn replace_windows(&mut self, windows: Vec<MappedWindow<'a, G::Time>>) -> Result<(), SchedulerError<G::Time>> {
+    RandomAccessScheduler::replace_windows(self, windows)
+  }
+}
+
+impl<'a, G> ReplaceWindows<'a, G::Time> for SequentialScheduler<'a, G> where G: TimeGenerator {
+  fn replace_windows(&mut self, windows: Vec<MappedWindow<'a, G::Time>>) -> Result<(), SchedulerError<G::Time>> {
+    SequentialScheduler::replace_windows(self, windows)
+  }
+}
+
+impl<'a, G> ReplaceWindows<'a, G::Time> for LayeredScheduler<'a, G> where G: TimeGenerator {
+  fn replace_windows(&mut self, windows: Vec<MappedWindow<'a, G::Time>>) -> Result<(), SchedulerError<G::Time>> {
+    LayeredScheduler::replace_windows(self, windows)
+  }
+}
+
+/// Watch a timeline file for modifications.
+///
+/// No background thread nor external service is involved: the file’s metadata is checked every time
+/// [`poll`] (or [`reload`]) is called, so you want to call it from your main loop.
+///
+/// [`poll`]: Self::poll
+/// [`reload`]: Self::reload
+#[derive(Clone, Debug)]
+pub struct TimelineWatcher {
+  path: PathBuf,
+  // modification time and length of the file when last polled, if it existed
+  stamp: Option<(SystemTime, u64)>
+}
+
+impl TimelineWatcher {
+  /// Watch the timeline file at `path`.
+  ///
+  /// The file, as it is when the watcher is created, is considered already loaded.
+  pub fn new<P>(path: P) -> Self where P: Into<PathBuf> {
+    let path = path.into();
+    let stamp = stamp(&path).ok();
+
+    TimelineWatcher { path, stamp }
+  }
+
+  /// Path of the watched file.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Check whether the file changed since the last poll.
+  ///
+  /// If the file cannot be accessed anymore, the error is returned once; the file is then considered
+  /// changed as soon as it’s accessible again.
+  pub fn poll(&mut self) -> Result<bool, io::Error> {
+    match stamp(&self.path) {
+      Ok(stamp) => {
+        let changed = self.stamp != Some(stamp);
+        self.stamp = Some(stamp);
+        Ok(changed)
+      }
+
+      Err(err) => {
+        if self.stamp.take().is_some() {
+          Err(err)
+        } else {
+          Ok(false)
+        }
+      }
+    }
+  }
+
+  /// Load the watched file.
+  ///
+  /// Its format is deduced from its extension (see [`Format::from_path`]).
+  ///
+  /// [`Format::from_path`]: crate::timeline::Format::from_path
+  pub fn load<T>(&self) -> Result<Timeline<T>, TimelineFileError> where T: DeserializeOwned {
+    Timeline::load(&self.path)
+  }
+
+  /// Reload the timeline into `scheduler` if the file changed since the last poll.
+  ///
+  /// Return [`None`] if the file didn’t change. Otherwise, the file is loaded, its windows are bound
+  /// to the actions of `registry` and replace the windows of `scheduler`. If any of that fails, the
+  /// error is returned and `scheduler` is left untouched.
+  pub fn reload<'a, T, S>(
+    &mut self,
+    registry: &ActionRegistry<'a, T>,
+    scheduler: &mut S
+  ) -> Option<Result<(), ReloadError<T>>>
+  where T: Copy + DeserializeOwned + 'a,
+        S: ReplaceWindows<'a, T> {
+    match self.poll() {
+      Ok(false) => None,
+      Ok(true) => Some(self.load().map_err(ReloadError::File).and_then(|timeline| {
+        let windows = registry.windows(&timeline)?;
+        scheduler.replace_windows(windows).map_err(TimelineError::Scheduler)?;
+        Ok(())
+      })),
+      Err(err) => Some(Err(ReloadError::File(TimelineFileError::Io(err))))
+    }
+  }
+}
+
+fn stamp(path: &Path) -> Result<(SystemTime, u64), io::Error> {
+  let metadata = fs::metadata(path)?;
+  Ok((metadata.modified()?, metadata.len()))
+}
+
+/// Errors that might occur when reloading a timeline.
+#[derive(Debug)]
+pub enum ReloadError<T> {
+  /// The timeline file couldn’t be loaded.
+  File(TimelineFileError),
+  /// The timeline couldn’t be bound or its windows are invalid.
+  Timeline(TimelineError<T>)
+}
+
+impl<T> From<TimelineFileError> for ReloadError<T> {
+  fn from(err: TimelineFileError) -> Self {
+    ReloadError::File(err)
+  }
+}
+
+impl<T> From<TimelineError<T>> for ReloadError<T> {
+  fn from(err: TimelineError<T>) -> Self {
+    ReloadError::Timeline(err)
+  }
+}
+
+impl<T> fmt::Display for ReloadError<T> where T: fmt::Debug {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      ReloadError::File(ref err) => err.fmt(f),
+      ReloadError::Timeline(ref err) => err.fmt(f)
+    }
+  }
+}
+
+impl<T> Error for ReloadError<T> where T: fmt::Debug + 'static {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match *self {
+      ReloadError::File(ref err) => Some(err),
+      ReloadError::Timeline(ref err) => Some(err)
+    }
+  }
+}
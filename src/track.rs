@@ -0,0 +1,224 @@
+//! Keyframe animation tracks.
+//!
+//! A [`Track`] is a list of [`Key`]s. Each key holds a value at a given time and the way to
+//! interpolate from that value to the value of the next key (see [`Interpolation`]). Sampling a
+//! track at any time gives an interpolated value, which makes tracks a natural fit to animate
+//! values from within the actions of mapped windows:
+//!
+//! ```
+//! use awoo::track::{Interpolation, Key, Track};
+//! use awoo::window::Window;
+//!
+//! let fade = Track::new(vec![
+//!   Key::new(0., 0., Interpolation::Linear),
+//!   Key::new(2., 1., Interpolation::Step),
+//! ]);
+//!
+//! let scene = Window::new(0., 4.).map(|t| {
+//!   let _opacity: f32 = fade.sample(t).unwrap();
+//!   // render the scene with opacity
+//! });
+//! ```
+//!
+//! Looking up the keys surrounding a given time is done with a binary search, which makes
+//! sampling a _O(log N)_ operation.
+//!
+//! [`Key`]: crate::track::Key
+//! [`Interpolation`]: crate::track::Interpolation
+//! [`Track`]: crate::track::Track
+
+#[cfg(feature = "serde")] use serde::{Deserialize, Serialize};
+use std::array;
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+
+use crate::easing::Easing;
+use crate::time::{Normalize, TimeOrd};
+
+/// Values that can be interpolated.
+pub trait Interpolate: Copy {
+  /// Linear interpolation between `a` and `b`, `t` being in _[0; 1]_.
+  fn lerp(a: Self, b: Self, t: f32) -> Self;
+
+  /// Cubic Hermite interpolation between `a` and `b`, `t` being in _[0; 1]_.
+  ///
+  /// `before` and `after` are the values surrounding `a` and `b`, used to compute the tangents at
+  /// `a` and `b` (Catmull-Rom spline).
+  fn cubic_hermite(before: Self, a: Self, b: Self, after: Self, t: f32) -> Self;
+}
+
+macro_rules! impl_Interpolate_float {
+  ($($t:ty),*) => {
+    $(
+      impl Interpolate for $t {
+        fn lerp(a: Self, b: Self, t: f32) -> Self {
+          let t = t as $t;
+          a + (b - a) * t
+        }
+
+        fn cubic_hermite(before: Self, a: Self, b: Self, after: Self, t: f32) -> Self {
+          let t = t as $t;
+          let t2 = t * t;
+          let t3 = t2 * t;
+
+          0.5 * (
+            2. * a +
+            (b - before) * t +
+            (2. * before - 5. * a + 4. * b - after) * t2 +
+            (3. * (a - b) + after - before) * t3
+          )
+        }
+      }
+
+      impl<const N: usize> Interpolate for [$t; N] {
+        fn lerp(a: Self, b: Self, t: f32) -> Self {
+          array::from_fn(|i| <$t>::lerp(a[i], b[i], t))
+        }
+
+        fn cubic_hermite(before: Self, a: Self, b: Self, after: Self, t: f32) -> Self {
+          array::from_fn(|i| <$t>::cubic_hermite(before[i], a[i], b[i], after[i], t))
+        }
+      }
+    )*
+  }
+}
+
+impl_Interpolate_float!(f32, f64);
+
+/// Interpolation mode of a [`Key`].
+///
+/// The interpolation mode of a key drives how values are interpolated from that key to the next
+/// one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Interpolation {
+  /// Hold the value of the key until the next key.
+  Step,
+  /// Linear interpolation.
+  Linear,
+  /// Cosine interpolation, smoothing the start and the end of the segment.
+  Cosine,
+  /// Cubic Hermite interpolation (Catmull-Rom spline), using the keys surrounding the segment.
+  CatmullRom,
+  /// Linear interpolation of the eased progress inside the segment.
+  Eased(Easing)
+}
+
+impl Interpolation {
+  /// Whether interpolating from a value to a greater one never goes back nor overshoots.
+  ///
+  /// [`Step`](Interpolation::Step) is monotonic, even though it jumps over the values between its
+  /// key and the next one.
+  ///
+  /// Catmull-Rom splines might overshoot, depending on the surrounding keys, and so might some
+  /// easing functions (see [`Easing::is_monotonic`]).
+  pub fn is_monotonic(self) -> bool {
+    match self {
+      Interpolation::Step | Interpolation::Linear | Interpolation::Cosine => true,
+      Interpolation::CatmullRom => false,
+      Interpolation::Eased(easing) => easing.is_monotonic()
+    }
+  }
+}
+
+/// A key in a [`Track`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Key<T, V> {
+  /// Time of the key.
+  pub t: T,
+  /// Value of the key.
+  pub value: V,
+  /// Interpolation to use from that key to the next one.
+  pub interpolation: Interpolation
+}
+
+impl<T, V> Key<T, V> {
+  /// Create a new key.
+  pub fn new(t: T, value: V, interpolation: Interpolation) -> Self {
+    Key {
+      t,
+      value,
+      interpolation
+    }
+  }
+}
+
+/// A keyframe animation track.
+///
+/// Keys are kept sorted by time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<Key<T, V>>", into = "Vec<Key<T, V>>"))]
+#[cfg_attr(feature = "serde", serde(bound(
+  serialize = "T: Clone + Serialize, V: Clone + Serialize",
+  deserialize = "T: TimeOrd + Deserialize<'de>, V: Deserialize<'de>"
+)))]
+pub struct Track<T, V> {
+  keys: Vec<Key<T, V>>
+}
+
+impl<T, V> Track<T, V> where T: TimeOrd {
+  /// Create a new track out of keys.
+  ///
+  /// The keys don’t have to be sorted.
+  pub fn new<K>(keys: K) -> Self where K: Into<Vec<Key<T, V>>> {
+    let mut keys = keys.into();
+    keys.sort_by(|a, b| a.t.time_cmp(&b.t));
+
+    Track { keys }
+  }
+
+  /// Keys of the track, sorted by time.
+  pub fn keys(&self) -> &[Key<T, V>] {
+    &self.keys
+  }
+
+  /// Sample the track at a given time.
+  ///
+  /// Before the first key, the value of the first key is returned. At or after the last key, the
+  /// value of the last key is returned. If the track has no key, [`None`] is returned.
+  pub fn sample(&self, t: T) -> Option<V> where T: Normalize, V: Interpolate {
+    // index of the first key after t
+    let i = self.keys.partition_point(|key| key.t.time_cmp(&t) != Ordering::Greater);
+
+    if i == 0 {
+      return self.keys.first().map(|key| key.value);
+    }
+
+    if i == self.keys.len() {
+      return self.keys.last().map(|key| key.value);
+    }
+
+    let a = &self.keys[i - 1];
+    let b = &self.keys[i];
+    let progress = (t - a.t).normalize(b.t - a.t);
+
+    let value = match a.interpolation {
+      Interpolation::Step => a.value,
+      Interpolation::Linear => V::lerp(a.value, b.value, progress),
+      Interpolation::Cosine => V::lerp(a.value, b.value, (1. - (progress * PI).cos()) * 0.5),
+      Interpolation::CatmullRom => {
+        let before = if i > 1 { self.keys[i - 2].value } else { a.value };
+        let after = self.keys.get(i + 1).map_or(b.value, |key| key.value);
+
+        V::cubic_hermite(before, a.value, b.value, after, progress)
+      }
+      Interpolation::Eased(easing) => V::lerp(a.value, b.value, easing.ease(progress))
+    };
+
+    Some(value)
+  }
+}
+
+impl<T, V> From<Vec<Key<T, V>>> for Track<T, V> where T: TimeOrd {
+  fn from(keys: Vec<Key<T, V>>) -> Self {
+    Track::new(keys)
+  }
+}
+
+impl<T, V> From<Track<T, V>> for Vec<Key<T, V>> {
+  fn from(track: Track<T, V>) -> Self {
+    track.keys
+  }
+}
@@ -11,11 +11,14 @@
 //! [`Window`]: crate::window::Window
 //! [`MappedWindow`]: crate::window::MappedWindow
 
-#[cfg(feature = "json")] use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")] use serde::{Deserialize, Serialize};
+
+use crate::easing::Easing;
+use crate::time::Normalize;
 
 /// A pure time window.
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Window<T> {
   /// Start time (inclusive) of the window.
   pub start: T,
@@ -36,14 +39,106 @@ impl<T> Window<T> {
   pub fn map<'a, F>(self, f: F) -> MappedWindow<'a, T> where F: FnMut(T) + 'a {
     MappedWindow {
       window: self,
-      carry: Box::new(f)
+      carry: Box::new(f),
+      on_enter: None,
+      on_leave: None,
+      layer: 0
     }
   }
+
+  /// Map an action to perform inside the [`Window`], giving it window-local time.
+  ///
+  /// This is like [`Window::map`], but the action receives a [`LocalTime`], which contains the time
+  /// elapsed since the start of the window and the normalized progress inside the window, along with
+  /// the absolute time.
+  pub fn map_local<'a, F>(self, mut f: F) -> MappedWindow<'a, T> where F: FnMut(LocalTime<T>) + 'a, T: Normalize + 'a {
+    let start = self.start;
+    let length = self.end - self.start;
+
+    self.map(move |time| {
+      let local = time - start;
+
+      f(LocalTime {
+        time,
+        local,
+        progress: local.normalize(length)
+      })
+    })
+  }
+}
+
+/// Window-local time.
+///
+/// Actions mapped with [`Window::map_local`] receive this instead of the absolute time only.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LocalTime<T> {
+  /// Absolute time.
+  pub time: T,
+  /// Time elapsed since the start of the window.
+  pub local: T,
+  /// Normalized progress inside the window, in _[0; 1)_.
+  pub progress: f32
+}
+
+impl<T> LocalTime<T> {
+  /// Normalized progress inside the window, eased with the given easing function.
+  pub fn eased(&self, easing: Easing) -> f32 {
+    easing.ease(self.progress)
+  }
 }
 
 /// Action scoped to time windows.
 pub struct MappedWindow<'a, T> {
   /// Window into which execute an action.
   pub window: Window<T>,
-  pub(crate) carry: Box<dyn FnMut(T) + 'a>
+  pub(crate) carry: Box<dyn FnMut(T) + 'a>,
+  pub(crate) on_enter: Option<Box<dyn FnMut(T) + 'a>>,
+  pub(crate) on_leave: Option<Box<dyn FnMut(T) + 'a>>,
+  pub(crate) layer: i32
+}
+
+impl<'a, T> MappedWindow<'a, T> {
+  /// Put the [`MappedWindow`] on a given layer.
+  ///
+  /// Layers are only meaningful to schedulers that accept overlapping windows: when several windows
+  /// are active at the same time, the ones on lower layers run first. The default layer is `0`.
+  pub fn with_layer(mut self, layer: i32) -> Self {
+    self.layer = layer;
+    self
+  }
+
+  /// Layer of the [`MappedWindow`].
+  pub fn layer(&self) -> i32 {
+    self.layer
+  }
+
+  /// Run a function when the [`MappedWindow`] becomes active.
+  ///
+  /// The function receives the time at which the scheduler notices the window is entered. It runs
+  /// once per transition, before the mapped action.
+  pub fn on_enter<F>(mut self, f: F) -> Self where F: FnMut(T) + 'a {
+    self.on_enter = Some(Box::new(f));
+    self
+  }
+
+  /// Run a function when the [`MappedWindow`] stops being active.
+  ///
+  /// The function receives the time at which the scheduler notices the window is left. It runs
+  /// once per transition, including when the timeline finishes or the scheduler is reset.
+  pub fn on_leave<F>(mut self, f: F) -> Self where F: FnMut(T) + 'a {
+    self.on_leave = Some(Box::new(f));
+    self
+  }
+
+  pub(crate) fn enter(&mut self, t: T) {
+    if let Some(ref mut on_enter) = self.on_enter {
+      on_enter(t);
+    }
+  }
+
+  pub(crate) fn leave(&mut self, t: T) {
+    if let Some(ref mut on_leave) = self.on_leave {
+      on_leave(t);
+    }
+  }
 }
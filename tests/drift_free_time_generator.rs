@@ -0,0 +1,46 @@
+use awoo::time::TimeGenerator;
+use awoo::time::drift_free::DriftFreeTimeGenerator;
+use std::time::Duration;
+
+#[test]
+fn no_drift() {
+  let mut gen = DriftFreeTimeGenerator::new(0., 0.01);
+
+  for i in 0..100_000 {
+    assert_eq!(gen.tick(), i as f32 * 0.01);
+  }
+
+  assert_eq!(gen.current(), 1000.);
+}
+
+#[test]
+fn untick_past_origin() {
+  let mut gen = DriftFreeTimeGenerator::new(1., 0.25);
+
+  for i in 0..8 {
+    assert_eq!(gen.untick(), 1. - i as f32 * 0.25);
+  }
+
+  assert_eq!(gen.count(), -8);
+  assert_eq!(gen.current(), -1.);
+
+  gen.reset();
+  assert_eq!(gen.count(), 0);
+  assert_eq!(gen.current(), 1.);
+}
+
+#[test]
+fn set_and_change_delta() {
+  let mut gen = DriftFreeTimeGenerator::new(Duration::ZERO, Duration::from_millis(10));
+
+  gen.tick();
+  gen.tick();
+  gen.change_delta(Duration::from_millis(100));
+  assert_eq!(gen.count(), 0);
+  assert_eq!(gen.tick(), Duration::from_millis(20));
+  assert_eq!(gen.current(), Duration::from_millis(120));
+
+  gen.set(Duration::from_secs(5));
+  gen.untick();
+  assert_eq!(gen.current(), Duration::from_millis(4900));
+}
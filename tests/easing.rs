@@ -0,0 +1,84 @@
+use awoo::easing::Easing;
+use awoo::track::{Interpolation, Key, Track};
+
+const EASINGS: &[Easing] = &[
+  Easing::Linear,
+  Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut,
+  Easing::CubicIn, Easing::CubicOut, Easing::CubicInOut,
+  Easing::QuartIn, Easing::QuartOut, Easing::QuartInOut,
+  Easing::ExpoIn, Easing::ExpoOut, Easing::ExpoInOut,
+  Easing::SineIn, Easing::SineOut, Easing::SineInOut,
+  Easing::ElasticIn, Easing::ElasticOut, Easing::ElasticInOut,
+  Easing::BounceIn, Easing::BounceOut, Easing::BounceInOut,
+  Easing::BackIn, Easing::BackOut, Easing::BackInOut,
+  Easing::CubicBezier(0.25, 0.1, 0.25, 1.),
+];
+
+fn close(a: f32, b: f32) -> bool {
+  (a - b).abs() < 1e-3
+}
+
+#[test]
+fn bounds() {
+  for &easing in EASINGS {
+    assert!(close(easing.ease(0.), 0.), "{:?} at 0: {}", easing, easing.ease(0.));
+    assert!(close(easing.ease(1.), 1.), "{:?} at 1: {}", easing, easing.ease(1.));
+  }
+}
+
+#[test]
+fn in_out_symmetry() {
+  for &easing in &[Easing::QuadInOut, Easing::CubicInOut, Easing::ExpoInOut, Easing::SineInOut, Easing::BounceInOut] {
+    assert!(close(easing.ease(0.5), 0.5), "{:?}", easing);
+
+    for i in 0..10 {
+      let t = i as f32 / 20.;
+      assert!(close(easing.ease(t), 1. - easing.ease(1. - t)), "{:?} at {}", easing, t);
+    }
+  }
+}
+
+#[test]
+fn cubic_bezier() {
+  // CSS’ linear and ease
+  for i in 0..=10 {
+    let t = i as f32 / 10.;
+    assert!(close(Easing::CubicBezier(0., 0., 1., 1.).ease(t), t));
+  }
+
+  assert!(close(Easing::CubicBezier(0.25, 0.1, 0.25, 1.).ease(0.5), 0.8024));
+  assert!(close(Easing::CubicBezier(0.42, 0., 1., 1.).ease(0.25), 0.0935));
+}
+
+#[test]
+fn eased_track() {
+  let track = Track::new(vec![
+    Key::new(0., 0., Interpolation::Eased(Easing::QuadIn)),
+    Key::new(2., 10., Interpolation::Step),
+  ]);
+
+  assert_eq!(track.sample(1.), Some(2.5));
+}
+
+#[test]
+fn monotonicity() {
+  assert!(Easing::CubicInOut.is_monotonic());
+  assert!(Easing::CubicBezier(0.42, 0., 0.58, 1.).is_monotonic());
+  assert!(!Easing::CubicBezier(0.42, -0.5, 0.58, 1.).is_monotonic());
+  assert!(!Easing::BackOut.is_monotonic());
+  assert!(!Easing::ElasticIn.is_monotonic());
+  assert!(!Easing::BounceIn.is_monotonic());
+  assert!(!Easing::BounceOut.is_monotonic());
+  assert!(!Easing::BounceInOut.is_monotonic());
+
+  // bouncing easing functions go back and forth
+  assert!(Easing::BounceOut.ease(0.5) < Easing::BounceOut.ease(1. / 2.75));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn serialization() {
+  let easings: Vec<Easing> = serde_json::from_str(r#"["QuadInOut", { "CubicBezier": [0.42, 0, 0.58, 1] }]"#).unwrap();
+
+  assert_eq!(easings, vec![Easing::QuadInOut, Easing::CubicBezier(0.42, 0., 0.58, 1.)]);
+}
@@ -0,0 +1,67 @@
+use awoo::time::TimeGenerator;
+use awoo::time::fixed_step::FixedStepTimeGenerator;
+use awoo::time::simple::SimpleTimeGenerator;
+use std::time::Duration;
+
+#[test]
+fn accumulate() {
+  let mut gen = FixedStepTimeGenerator::new(SimpleTimeGenerator::new(0u64, 1), 10, 100);
+  let alpha = gen.alpha_handle();
+
+  assert_eq!(gen.accumulate(25), 2);
+  assert_eq!(gen.alpha(), 0.5);
+  assert_eq!(gen.accumulate(5), 1);
+  assert_eq!(alpha.get(), 0.);
+
+  for _ in 0..3 {
+    gen.tick();
+  }
+
+  // the wrapped generator ticks by fixed steps
+  assert_eq!(gen.current(), 30);
+}
+
+#[test]
+fn spiral_of_death() {
+  let mut gen = FixedStepTimeGenerator::new(
+    SimpleTimeGenerator::new(Duration::ZERO, Duration::ZERO),
+    Duration::from_millis(10),
+    Duration::from_millis(250)
+  );
+
+  assert_eq!(gen.accumulate(Duration::from_secs(3)), 25);
+  assert_eq!(gen.alpha(), 0.);
+
+  gen.accumulate(Duration::from_millis(4));
+  gen.reset();
+  assert_eq!(gen.alpha(), 0.);
+  assert_eq!(gen.accumulate(Duration::from_millis(9)), 0);
+}
+
+#[test]
+#[should_panic]
+fn zero_step() {
+  FixedStepTimeGenerator::new(SimpleTimeGenerator::new(0u64, 1), 0, 100);
+}
+
+#[test]
+#[should_panic]
+fn nan_step() {
+  let mut gen = FixedStepTimeGenerator::new(SimpleTimeGenerator::new(0., 1.), 0.1, 1.);
+  gen.change_delta(f64::NAN);
+}
+
+#[test]
+fn large_frame_time() {
+  let mut gen = FixedStepTimeGenerator::new(SimpleTimeGenerator::new(0u64, 1), 1, u64::MAX / 4);
+
+  assert_eq!(gen.accumulate(1 << 40), 1 << 40);
+  assert_eq!(gen.accumulate(u64::MAX), (u64::MAX / 4) as usize);
+  assert_eq!(gen.alpha(), 0.);
+}
+
+#[test]
+#[should_panic]
+fn infinite_max_frame_time() {
+  FixedStepTimeGenerator::new(SimpleTimeGenerator::new(0., 1.), 0.1, f64::INFINITY);
+}
@@ -0,0 +1,85 @@
+use awoo::scheduler::StepResult;
+use awoo::scheduler::layered::LayeredScheduler;
+use awoo::time::simple::SimpleF32TimeGenerator;
+use awoo::window::Window;
+use std::cell::RefCell;
+
+#[test]
+fn overlapping() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(1., 3.).map(|t| trace.borrow_mut().push(('b', t))),
+    Window::new(0., 4.).map(|t| trace.borrow_mut().push(('a', t))),
+    Window::new(2., 3.).map(|t| trace.borrow_mut().push(('c', t))),
+  ];
+  let mut scheduler = LayeredScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  scheduler.schedule();
+
+  assert_eq!(*trace.borrow(), vec![
+    ('a', 0.),
+    ('a', 1.), ('b', 1.),
+    ('a', 2.), ('b', 2.), ('c', 2.),
+    ('a', 3.),
+  ]);
+}
+
+#[test]
+fn layers() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 2.).map(|_| trace.borrow_mut().push("camera")).with_layer(1),
+    Window::new(1., 2.).map(|_| trace.borrow_mut().push("scene b")),
+    Window::new(0., 2.).map(|_| trace.borrow_mut().push("scene a")),
+  ];
+  let mut scheduler = LayeredScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert_eq!(scheduler.seek(1.5), 3);
+  assert_eq!(*trace.borrow(), vec!["scene a", "scene b", "camera"]);
+}
+
+#[test]
+fn many_windows() {
+  let hits = RefCell::new(vec![0; 100]);
+  let windows = (0..100).map(|i| {
+    let hits = &hits;
+    Window::new(i as f32, i as f32 + (i % 7) as f32 + 0.5).map(move |_| hits.borrow_mut()[i] += 1)
+  }).collect::<Vec<_>>();
+  let mut scheduler = LayeredScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  for t in 0..110 {
+    let expected = (0..100).filter(|&i| {
+      let start = i as f32;
+      start <= t as f32 && (t as f32) < start + (i % 7) as f32 + 0.5
+    }).count();
+
+    assert_eq!(scheduler.evaluate_at(t as f32), expected);
+  }
+
+  for (i, &count) in hits.borrow().iter().enumerate() {
+    assert_eq!(count, i % 7 + 1);
+  }
+
+  assert_eq!(scheduler.step_at(105.), StepResult::Finished);
+}
+
+#[test]
+fn replace_windows() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 4.).map(|t| trace.borrow_mut().push(('a', t))).on_leave(|t| trace.borrow_mut().push(('A', t))),
+  ];
+
+  let mut scheduler = LayeredScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+  scheduler.step();
+
+  let windows = vec![
+    Window::new(0., 4.).map(|t| trace.borrow_mut().push(('b', t))),
+    Window::new(1., 2.).map(|t| trace.borrow_mut().push(('c', t))),
+  ];
+  scheduler.replace_windows(windows).unwrap();
+  assert_eq!(scheduler.step(), StepResult::Continue);
+  drop(scheduler);
+
+  assert_eq!(trace.into_inner(), vec![('a', 0.), ('A', 1.), ('b', 1.), ('c', 1.)]);
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "midi")]
+
+use awoo::midi::{MidiCue, MidiError, MidiImport, MidiNote};
+use awoo::window::Window;
+use midly::num::{u15, u24, u28, u4, u7};
+use midly::{Format, Fps, Header, MetaMessage, MidiMessage, Smf, Timing, TrackEvent, TrackEventKind};
+
+// build a track out of (absolute tick, event) pairs
+fn track(events: Vec<(u32, TrackEventKind<'static>)>) -> Vec<TrackEvent<'static>> {
+  let mut last = 0;
+  let mut track: Vec<_> = events
+    .into_iter()
+    .map(|(tick, kind)| {
+      let delta = u28::new(tick - last);
+      last = tick;
+      TrackEvent { delta, kind }
+    })
+    .collect();
+
+  track.push(TrackEvent {
+    delta: u28::new(0),
+    kind: TrackEventKind::Meta(MetaMessage::EndOfTrack)
+  });
+  track
+}
+
+fn tempo(micros_per_beat: u32) -> TrackEventKind<'static> {
+  TrackEventKind::Meta(MetaMessage::Tempo(u24::new(micros_per_beat)))
+}
+
+fn marker(name: &'static str) -> TrackEventKind<'static> {
+  TrackEventKind::Meta(MetaMessage::Marker(name.as_bytes()))
+}
+
+fn note_on(channel: u8, key: u8, vel: u8) -> TrackEventKind<'static> {
+  TrackEventKind::Midi {
+    channel: u4::new(channel),
+    message: MidiMessage::NoteOn { key: u7::new(key), vel: u7::new(vel) }
+  }
+}
+
+fn note_off(channel: u8, key: u8) -> TrackEventKind<'static> {
+  TrackEventKind::Midi {
+    channel: u4::new(channel),
+    message: MidiMessage::NoteOff { key: u7::new(key), vel: u7::new(0) }
+  }
+}
+
+fn write(format: Format, timing: Timing, tracks: Vec<Vec<TrackEvent<'static>>>) -> Vec<u8> {
+  let mut smf = Smf::new(Header::new(format, timing));
+  smf.tracks = tracks;
+
+  let mut bytes = Vec::new();
+  smf.write_std(&mut bytes).unwrap();
+  bytes
+}
+
+fn note(channel: u8, key: u8, velocity: u8, start: f64, end: f64) -> MidiNote {
+  MidiNote {
+    channel,
+    key,
+    velocity,
+    window: Window::new(start, end)
+  }
+}
+
+// two tracks at 480 ticks per beat; 120 BPM for the first two beats, then 240 BPM
+fn song() -> Vec<u8> {
+  let conductor = track(vec![
+    (0, tempo(500_000)),
+    (0, marker("intro")),
+    (960, tempo(250_000)),
+    (960, marker(" drop ")),
+    (1920, TrackEventKind::Meta(MetaMessage::Text(b"end")))
+  ]);
+
+  let notes = track(vec![
+    (0, note_on(9, 36, 100)),
+    (100, note_on(0, 62, 10)),
+    (100, note_off(0, 62)),
+    (480, note_off(9, 36)),
+    (480, note_on(0, 60, 80)),
+    (720, note_on(0, 60, 90)),
+    (960, note_on(9, 36, 110)),
+    (1440, note_on(9, 36, 0))
+  ]);
+
+  write(Format::Parallel, Timing::Metrical(u15::new(480)), vec![conductor, notes])
+}
+
+#[test]
+fn notes_and_cues() {
+  let song = MidiImport::parse(&song()).unwrap();
+
+  assert_eq!(song.notes, vec![
+    note(9, 36, 100, 0., 0.5),
+    note(0, 60, 80, 0.5, 0.75),
+    note(0, 60, 90, 0.75, 1.25),
+    note(9, 36, 110, 1., 1.25)
+  ]);
+
+  assert_eq!(song.cues, vec![
+    MidiCue { t: 0., name: "intro".to_owned() },
+    MidiCue { t: 1., name: "drop".to_owned() }
+  ]);
+
+  assert_eq!(song.duration, 1.5);
+  assert_eq!(song.windows(9, 36), vec![Window::new(0., 0.5), Window::new(1., 1.25)]);
+  assert_eq!(song.windows(9, 37), vec![]);
+}
+
+#[test]
+fn timecode_timing() {
+  let notes = track(vec![
+    (0, tempo(1_000_000)),
+    (500, note_on(2, 64, 127)),
+    (1500, note_off(2, 64))
+  ]);
+  let bytes = write(Format::SingleTrack, Timing::Timecode(Fps::Fps25, 40), vec![notes]);
+
+  assert_eq!(MidiImport::parse(&bytes).unwrap().notes, vec![note(2, 64, 127, 0.5, 1.5)]);
+}
+
+#[test]
+fn sequential_tracks() {
+  let first = track(vec![(0, tempo(1_000_000)), (0, note_on(0, 60, 1)), (480, note_off(0, 60))]);
+  let second = track(vec![(0, marker("second")), (0, note_on(0, 60, 2)), (480, note_off(0, 60))]);
+  let bytes = write(Format::Sequential, Timing::Metrical(u15::new(480)), vec![first, second]);
+  let song = MidiImport::parse(&bytes).unwrap();
+
+  // the tempo of the first track doesn’t apply to the second one
+  assert_eq!(song.notes, vec![note(0, 60, 1, 0., 1.), note(0, 60, 2, 1., 1.5)]);
+  assert_eq!(song.cues, vec![MidiCue { t: 1., name: "second".to_owned() }]);
+  assert_eq!(song.duration, 1.5);
+}
+
+#[test]
+fn same_note_on_parallel_tracks() {
+  let first = track(vec![(0, note_on(0, 60, 1)), (960, note_off(0, 60))]);
+  let second = track(vec![(480, note_on(0, 60, 2)), (1440, note_off(0, 60))]);
+  let bytes = write(Format::Parallel, Timing::Metrical(u15::new(480)), vec![first, second]);
+  let song = MidiImport::parse(&bytes).unwrap();
+
+  // the second note-on releases the first note, and the first note-off the second one
+  assert_eq!(song.notes, vec![note(0, 60, 1, 0., 0.5), note(0, 60, 2, 0.5, 1.)]);
+  assert_eq!(song.windows(0, 60), vec![Window::new(0., 0.5), Window::new(0.5, 1.)]);
+  assert_eq!(song.duration, 1.5);
+}
+
+#[test]
+fn held_note_released_at_end_of_track() {
+  let first = track(vec![(0, note_on(0, 60, 1)), (480, marker("end of first"))]);
+  let second = track(vec![(960, note_on(0, 60, 2)), (1440, note_off(0, 60))]);
+  let bytes = write(Format::Parallel, Timing::Metrical(u15::new(480)), vec![first, second]);
+
+  // the note of the first track is released when that track ends, not by the later note-on
+  assert_eq!(MidiImport::parse(&bytes).unwrap().notes, vec![note(0, 60, 1, 0., 0.5), note(0, 60, 2, 1., 1.5)]);
+}
+
+#[test]
+fn invalid_file() {
+  assert!(matches!(MidiImport::parse(b"not a MIDI file"), Err(MidiError::Parse(_))));
+  assert!(matches!(MidiImport::load("/does/not/exist.mid"), Err(MidiError::Io(_))));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn timeline() {
+  use awoo::time::simple::SimpleTimeGenerator;
+  use awoo::timeline::ActionRegistry;
+  use std::cell::RefCell;
+
+  let timeline = MidiImport::parse(&song()).unwrap().to_timeline();
+  let names: Vec<_> = timeline.windows.iter().map(|window| (window.name.as_str(), window.layer)).collect();
+
+  assert_eq!(names, vec![
+    ("cue:intro", 0),
+    ("cue:drop", 0),
+    ("note:9:36", 10),
+    ("note:0:60", 1),
+    ("note:0:60", 1),
+    ("note:9:36", 10)
+  ]);
+  assert_eq!(timeline.windows[1].window(), Window::new(1., 1.5));
+  assert_eq!(timeline.windows[2].metadata["velocity"], "100");
+
+  let hits = RefCell::new(Vec::new());
+  let mut registry = ActionRegistry::new();
+  registry
+    .register("cue:intro", |_| hits.borrow_mut().push("intro"))
+    .register("cue:drop", |_| hits.borrow_mut().push("drop"))
+    .register("note:9:36", |_| hits.borrow_mut().push("kick"))
+    .register("note:0:60", |_| hits.borrow_mut().push("c4"));
+
+  let mut scheduler = registry.layered_scheduler(SimpleTimeGenerator::new(0., 0.25), &timeline).unwrap();
+  scheduler.evaluate_at(1.);
+  drop(scheduler);
+
+  assert_eq!(*hits.borrow(), vec!["drop", "c4", "kick"]);
+}
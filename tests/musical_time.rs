@@ -0,0 +1,97 @@
+use awoo::scheduler::SequentialScheduler;
+use awoo::time::TimeGenerator;
+use awoo::time::musical::{MusicalTime, MusicalTimeGenerator, TempoMap};
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::window::Window;
+use std::cell::RefCell;
+
+#[test]
+fn constant_tempo() {
+  let map = TempoMap::new(480, 120., 4, 4);
+
+  assert_eq!(map.time_to_seconds(MusicalTime::new(0, 1, 0)), 0.5);
+  assert_eq!(map.time_to_seconds(MusicalTime::new(1, 0, 0)), 2.);
+  assert_eq!(map.time_to_seconds(MusicalTime::new(1, 0, 240)), 2.25);
+  assert_eq!(map.seconds_to_time(2.25), MusicalTime::new(1, 0, 240));
+  assert_eq!(map.seconds_to_time(-1.), MusicalTime::new(0, 0, 0));
+}
+
+#[test]
+fn tempo_changes() {
+  let mut map = TempoMap::new(480, 120., 4, 4);
+  map.add_tempo_change(MusicalTime::new(2, 0, 0), 60.);
+
+  assert_eq!(map.time_to_seconds(MusicalTime::new(2, 0, 0)), 4.);
+  assert_eq!(map.time_to_seconds(MusicalTime::new(2, 1, 0)), 5.);
+  assert_eq!(map.seconds_to_time(6.5), MusicalTime::new(2, 2, 240));
+  assert_eq!(map.tempo_at(MusicalTime::new(1, 3, 479)), 120.);
+  assert_eq!(map.tempo_at(MusicalTime::new(3, 0, 0)), 60.);
+
+  // replacing a change
+  map.add_tempo_change(MusicalTime::new(2, 0, 0), 240.);
+  assert_eq!(map.time_to_seconds(MusicalTime::new(3, 0, 0)), 5.);
+}
+
+#[test]
+fn signature_changes() {
+  let mut map = TempoMap::new(480, 120., 4, 4);
+  map.add_signature_change(1, 6, 8);
+  map.add_tempo_change(MusicalTime::new(2, 0, 0), 60.);
+
+  assert_eq!(map.signature_at(0), (4, 4));
+  assert_eq!(map.signature_at(5), (6, 8));
+
+  // a bar of 6/8 is three quarter notes long
+  assert_eq!(map.time_to_ticks(MusicalTime::new(2, 0, 0)), 1920 + 1440);
+  assert_eq!(map.time_to_ticks(MusicalTime::new(1, 1, 0)), 1920 + 240);
+  assert_eq!(map.ticks_to_time(1920 + 1440 + 250), MusicalTime::new(2, 1, 10));
+  assert_eq!(map.time_to_seconds(MusicalTime::new(2, 0, 0)), 3.5);
+  assert_eq!(map.time_to_seconds(MusicalTime::new(3, 0, 0)), 6.5);
+}
+
+#[test]
+fn scheduled_in_beats() {
+  let map = TempoMap::new(4, 60., 4, 4);
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(MusicalTime::new(0, 1, 0), MusicalTime::new(0, 2, 0)).map(|t| trace.borrow_mut().push(t)),
+  ];
+  let gen = MusicalTimeGenerator::new(SimpleTimeGenerator::new(0., 0.5), map);
+  let mut scheduler = SequentialScheduler::new(gen, windows).unwrap();
+
+  scheduler.schedule();
+  assert_eq!(*trace.borrow(), vec![MusicalTime::new(0, 1, 0), MusicalTime::new(0, 1, 2)]);
+
+  scheduler.time_gen_mut().set(MusicalTime::new(1, 0, 0));
+  assert_eq!(scheduler.time_gen().seconds(), 4.);
+}
+
+#[test]
+#[should_panic]
+fn zero_numerator() {
+  TempoMap::new(480, 120., 0, 4);
+}
+
+#[test]
+#[should_panic]
+fn zero_denominator() {
+  TempoMap::new(480, 120., 4, 0);
+}
+
+#[test]
+#[should_panic]
+fn zero_ppq() {
+  TempoMap::new(0, 120., 4, 4);
+}
+
+#[test]
+#[should_panic]
+fn invalid_signature_change() {
+  TempoMap::new(480, 120., 4, 4).add_signature_change(2, 3, 0);
+}
+
+#[test]
+#[should_panic]
+fn invalid_tempo_change() {
+  TempoMap::new(480, 120., 4, 4).add_tempo_change(MusicalTime::default(), f64::NAN);
+}
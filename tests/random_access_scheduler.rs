@@ -0,0 +1,128 @@
+use awoo::scheduler::{Interrupt, RandomAccessScheduler, SchedulerError, StepResult};
+use awoo::time::TimeOrd;
+use awoo::time::simple::SimpleF32TimeGenerator;
+use awoo::window::Window;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+#[test]
+fn overlapping() {
+  let a = Window::new(4., 5.).map(|_| ());
+  let b = Window::new(1., 3.).map(|_| ());
+  let c = Window::new(0., 2.).map(|_| ());
+  let err = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), vec![a, b, c]).err();
+
+  assert_eq!(err, Some(SchedulerError::Overlapping {
+    first: 2,
+    first_window: Window::new(0., 2.),
+    second: 1,
+    second_window: Window::new(1., 3.)
+  }));
+  assert_eq!(err.unwrap().to_string(), "window #2 [0.0, 2.0) overlaps with window #1 [1.0, 3.0)");
+}
+
+#[test]
+fn invalid_windows() {
+  let a = Window::new(0., 1.).map(|_| ());
+  let b = Window::new(3., 2.).map(|_| ());
+  let err = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), vec![a, b]).err();
+
+  assert_eq!(err, Some(SchedulerError::EmptyWindow { index: 1, window: Window::new(3., 2.) }));
+
+  let a = Window::new(0., 1.).map(|_| ());
+  let b = Window::new(1., f32::NAN).map(|_| ());
+
+  match RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), vec![a, b]) {
+    Err(SchedulerError::IncomparableBounds { index: 1, .. }) => (),
+    _ => panic!("NaN bounds should be rejected")
+  }
+}
+
+#[test]
+fn step() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 1.).map(|t| trace.borrow_mut().push(('a', t))),
+    Window::new(2., 3.).map(|t| trace.borrow_mut().push(('b', t))),
+  ];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert_eq!(scheduler.step(), StepResult::Continue);
+  assert_eq!(scheduler.step(), StepResult::Continue);
+  assert_eq!(scheduler.step(), StepResult::Finished);
+  assert_eq!(*trace.borrow(), vec![('a', 0.), ('b', 2.)]);
+
+  assert_eq!(scheduler.step_at(0.), StepResult::Continue);
+  assert_eq!(trace.borrow().last(), Some(&('a', 0.)));
+}
+
+#[test]
+fn schedule_interrupted() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![Window::new(0., 10.).map(|t| trace.borrow_mut().push(t))];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  scheduler.interruptible_with(|t| if t >= 3. { Interrupt::Break } else { Interrupt::Continue });
+  scheduler.schedule();
+  assert_eq!(*trace.borrow(), vec![0., 1., 2.]);
+
+  assert_eq!(scheduler.step(), StepResult::Interrupted);
+}
+
+#[test]
+fn empty_timeline_is_finished() {
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), Vec::new()).unwrap();
+
+  assert_eq!(scheduler.step(), StepResult::Finished);
+  scheduler.schedule();
+}
+
+#[test]
+fn seek() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 1.).map(|t| trace.borrow_mut().push(('a', t))),
+    Window::new(2., 3.).map(|t| trace.borrow_mut().push(('b', t))),
+  ];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert!(scheduler.seek(2.5));
+  assert!(!scheduler.seek(1.5));
+  assert!(scheduler.evaluate_at(0.25));
+  assert_eq!(*trace.borrow(), vec![('b', 2.5), ('a', 0.25)]);
+
+  // seeking moved the time generator
+  scheduler.step();
+  assert_eq!(trace.borrow().len(), 2);
+  scheduler.step();
+  assert_eq!(trace.borrow().last(), Some(&('b', 2.5)));
+}
+
+#[test]
+fn nan_time() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 1.).map(|t| trace.borrow_mut().push(t)),
+    Window::new(1., 2.).map(|t| trace.borrow_mut().push(t)),
+  ];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert!(!scheduler.evaluate_at(f32::NAN));
+  assert_eq!(scheduler.step_at(f32::NAN), StepResult::Finished);
+  assert!(trace.borrow().is_empty());
+}
+
+#[test]
+fn negative_zero() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(-1., 0.).map(|_| trace.borrow_mut().push('a')),
+    Window::new(0., 1.).map(|_| trace.borrow_mut().push('b')),
+  ];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert!(scheduler.evaluate_at(-0.));
+  assert_eq!(*trace.borrow(), vec!['b']);
+  assert_eq!((-0f32).time_cmp(&0.), Ordering::Equal);
+  assert_eq!(f32::NAN.time_cmp(&f32::INFINITY), Ordering::Greater);
+}
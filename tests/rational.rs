@@ -0,0 +1,115 @@
+use awoo::scheduler::SequentialScheduler;
+use awoo::time::rational::{Rational, RationalError};
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::time::{Arithmetic, Normalize, TimeGenerator};
+use awoo::window::Window;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+
+#[test]
+fn normalization() {
+  let r = Rational::new(4, -6);
+
+  assert_eq!(r.numer(), -2);
+  assert_eq!(r.denom(), 3);
+  assert_eq!(r, Rational::new(-2, 3));
+  assert_eq!(Rational::new(0, 5), Rational::from(0));
+}
+
+#[test]
+#[should_panic]
+fn zero_denominator() {
+  Rational::new(1, 0);
+}
+
+#[test]
+fn ordering_and_arithmetic() {
+  let third = Rational::new(1, 3);
+  let half = Rational::new(1, 2);
+
+  assert!(third < half);
+  assert!(-half < -third);
+  assert_eq!(third + half, Rational::new(5, 6));
+  assert_eq!(third - half, Rational::new(-1, 6));
+  assert_eq!(third * half, Rational::new(1, 6));
+  assert_eq!(third / half, Rational::new(2, 3));
+  assert_eq!(third.times(6), Rational::from(2));
+  assert_eq!(Rational::new(1, 4).normalize(Rational::new(1, 2)), 0.5);
+
+  // no overflow for large denominators
+  let a = Rational::new(1, i64::MAX);
+  let b = Rational::new(1, i64::MAX - 1);
+  assert!(a < b);
+  assert!((a + b).to_f64() > 0.);
+}
+
+#[test]
+fn float_conversions() {
+  assert_eq!(Rational::new(1, 4).to_f32(), 0.25);
+  assert_eq!(f64::from(Rational::new(-3, 2)), -1.5);
+
+  assert_eq!(Rational::try_from(0.375), Ok(Rational::new(3, 8)));
+  assert_eq!(Rational::try_from(-2f32), Ok(Rational::from(-2)));
+  assert_eq!(Rational::try_from(f64::NAN), Err(RationalError::NotFinite));
+  assert_eq!(Rational::try_from(1e30), Err(RationalError::Overflow));
+  assert_eq!(Rational::try_from(1e-30), Err(RationalError::Overflow));
+
+  // exact conversion of 0.1 gives a binary fraction; approximation recovers a tenth
+  assert_ne!(Rational::try_from(0.1), Ok(Rational::new(1, 10)));
+  assert_eq!(Rational::approximate(0.1, 1000), Some(Rational::new(1, 10)));
+  assert_eq!(Rational::approximate(1. / 3., 1000), Some(Rational::new(1, 3)));
+  assert_eq!(Rational::approximate(std::f64::consts::PI, 1000), Some(Rational::new(355, 113)));
+  assert_eq!(Rational::approximate(-2.5, 10), Some(Rational::new(-5, 2)));
+  assert_eq!(Rational::approximate(f64::INFINITY, 10), None);
+}
+
+#[test]
+fn parse_and_print() {
+  assert_eq!(Rational::new(1, 3).to_string(), "1/3");
+  assert_eq!(Rational::from(-4).to_string(), "-4");
+  assert_eq!("2/6".parse(), Ok(Rational::new(1, 3)));
+  assert_eq!("7".parse(), Ok(Rational::from(7)));
+  assert_eq!("1/0".parse::<Rational>(), Err(RationalError::ZeroDenominator));
+  assert!(matches!("1/a".parse::<Rational>(), Err(RationalError::Malformed(_))));
+}
+
+#[test]
+fn exact_boundaries() {
+  let hits = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(Rational::from(0), Rational::new(1, 3)).map(|t| hits.borrow_mut().push((0, t))),
+    Window::new(Rational::new(1, 3), Rational::new(2, 3)).map(|t| hits.borrow_mut().push((1, t))),
+  ];
+
+  let gen = SimpleTimeGenerator::new(Rational::from(0), Rational::new(1, 30));
+  let mut scheduler = SequentialScheduler::new(gen, windows).unwrap();
+  scheduler.schedule();
+  drop(scheduler);
+
+  let hits = hits.into_inner();
+  assert_eq!(hits.len(), 20);
+  assert!(hits[.. 10].iter().all(|&(w, _)| w == 0));
+  assert_eq!(hits[10], (1, Rational::new(1, 3)));
+}
+
+#[test]
+fn generator() {
+  let mut gen = SimpleTimeGenerator::new(Rational::from(0), Rational::new(1, 30));
+
+  for _ in 0 .. 30 {
+    gen.tick();
+  }
+
+  assert_eq!(gen.current(), Rational::from(1));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn serialize() {
+  let r = Rational::new(1, 3);
+  let json = serde_json::to_string(&r).unwrap();
+
+  assert_eq!(json, "\"1/3\"");
+  assert_eq!(serde_json::from_str::<Rational>(&json).unwrap(), r);
+  assert!(serde_json::from_str::<Rational>("\"1/0\"").is_err());
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "rocket")]
+
+use awoo::rocket::client::RocketClient;
+use awoo::rocket::{track_path, RocketError, RocketInterpolation, RocketKey, RocketTrack};
+use awoo::time::TimeGenerator;
+use awoo::time::simple::SimpleTimeGenerator;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn assert_about(a: f32, b: f32) {
+  assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+}
+
+#[test]
+fn interpolations() {
+  let mut track = RocketTrack::new("x");
+  assert_eq!(track.sample(3.), 0.);
+
+  track.set_key(RocketKey::new(0, 0., RocketInterpolation::Smooth));
+  track.set_key(RocketKey::new(4, 1., RocketInterpolation::Ramp));
+  track.set_key(RocketKey::new(8, 0., RocketInterpolation::Step));
+  track.set_key(RocketKey::new(12, 1., RocketInterpolation::Linear));
+  track.set_key(RocketKey::new(16, 3., RocketInterpolation::Linear));
+
+  assert_about(track.sample(1.), 0.15625);
+  assert_about(track.sample(2.), 0.5);
+  assert_about(track.sample(6.), 0.75);
+  assert_about(track.sample(10.), 0.);
+  assert_about(track.sample(14.), 2.);
+  assert_about(track.sample(20.), 3.);
+
+  // replacing and deleting keys
+  track.set_key(RocketKey::new(16, 5., RocketInterpolation::Linear));
+  assert_about(track.sample(14.), 3.);
+  assert_eq!(track.delete_key(16).map(|key| key.value), Some(5.));
+  assert_eq!(track.delete_key(16), None);
+  assert_eq!(track.keys().len(), 4);
+}
+
+#[test]
+fn track_file() {
+  let mut bytes = vec![2, 0, 0, 0];
+  bytes.extend_from_slice(&[4, 0, 0, 0]);
+  bytes.extend_from_slice(&2f32.to_le_bytes());
+  bytes.push(1);
+  bytes.extend_from_slice(&[0, 0, 0, 0]);
+  bytes.extend_from_slice(&1f32.to_le_bytes());
+  bytes.push(3);
+
+  let track = RocketTrack::read("x", bytes.as_slice()).unwrap();
+  assert_eq!(track.keys(), &[
+    RocketKey::new(0, 1., RocketInterpolation::Ramp),
+    RocketKey::new(4, 2., RocketInterpolation::Linear)
+  ]);
+
+  let mut written = Vec::new();
+  track.write(&mut written).unwrap();
+  assert_eq!(RocketTrack::read("x", written.as_slice()).unwrap(), track);
+
+  bytes[12] = 7;
+  assert!(matches!(RocketTrack::read("x", bytes.as_slice()), Err(RocketError::UnknownInterpolation(7))));
+  assert!(matches!(RocketTrack::read("x", &bytes[.. 6]), Err(RocketError::Io(_))));
+}
+
+#[test]
+fn path() {
+  assert_eq!(track_path("data/sync", "camera:fov"), Path::new("data/sync_camera:fov.track"));
+}
+
+// poll the client until a condition holds
+fn update_until<G, F>(client: &mut RocketClient, gen: &mut G, cond: F) where G: TimeGenerator<Time = f64>, F: Fn(&RocketClient, &G) -> bool {
+  let deadline = Instant::now() + Duration::from_secs(5);
+
+  while !cond(client, gen) {
+    assert!(Instant::now() < deadline, "timed out");
+    client.update(gen).unwrap();
+    thread::sleep(Duration::from_millis(1));
+  }
+}
+
+#[test]
+fn client() {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let addr = listener.local_addr().unwrap();
+  let prefix = std::env::temp_dir().join(format!("awoo-rocket-{}", std::process::id()));
+
+  let editor = thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+
+    let mut greeting = [0; 19];
+    stream.read_exact(&mut greeting).unwrap();
+    assert_eq!(&greeting, b"hello, synctracker!");
+    stream.write_all(b"hello, demo!").unwrap();
+
+    let mut get_track = [0; 8];
+    stream.read_exact(&mut get_track).unwrap();
+    assert_eq!(&get_track, b"\x02\x00\x00\x00\x03fov");
+
+    // set two keys, move to row 16 and play, sending the keys byte by byte
+    let mut cmds = Vec::new();
+    for &(row, value) in &[(0u32, 60f32), (8, 90.)] {
+      cmds.push(0);
+      cmds.extend_from_slice(&0u32.to_be_bytes());
+      cmds.extend_from_slice(&row.to_be_bytes());
+      cmds.extend_from_slice(&value.to_bits().to_be_bytes());
+      cmds.push(1);
+    }
+
+    for byte in cmds {
+      stream.write_all(&[byte]).unwrap();
+    }
+
+    stream.write_all(&[3, 0, 0, 0, 16, 4, 0]).unwrap();
+
+    // the demo plays and reports its row
+    let mut set_row = [0; 5];
+    stream.read_exact(&mut set_row).unwrap();
+    assert_eq!(set_row[0], 3);
+    assert!(u32::from_be_bytes([set_row[1], set_row[2], set_row[3], set_row[4]]) > 16);
+
+    // pause, delete a key and save
+    stream.write_all(&[4, 1]).unwrap();
+    stream.write_all(&[1, 0, 0, 0, 0, 0, 0, 0, 8]).unwrap();
+    stream.write_all(&[5]).unwrap();
+
+    // wait for the demo to disconnect
+    let mut buf = [0; 1];
+    stream.read_exact(&mut buf).ok();
+  });
+
+  let mut client = RocketClient::connect(addr, &prefix, 8.).unwrap();
+  let mut gen = SimpleTimeGenerator::new(0., 0.5);
+  assert!(client.is_paused());
+
+  client.request_track("fov").unwrap();
+  client.request_track("fov").unwrap();
+  assert_eq!(client.tracks().len(), 1);
+
+  update_until(&mut client, &mut gen, |client, _| !client.is_paused());
+  assert_eq!(gen.current(), 2.);
+  assert_about(client.track("fov").unwrap().sample(4.), 75.);
+
+  gen.tick();
+  client.update(&mut gen).unwrap();
+
+  update_until(&mut client, &mut gen, |client, _| client.track("fov").unwrap().keys().len() == 1);
+  assert!(client.is_paused());
+
+  update_until(&mut client, &mut gen, |_, _| track_path(&prefix, "fov").exists());
+
+  let path = track_path(&prefix, "fov");
+  let saved = RocketTrack::load("fov", &path).unwrap();
+  std::fs::remove_file(&path).unwrap();
+  assert_eq!(saved.keys(), &[RocketKey::new(0, 60., RocketInterpolation::Linear)]);
+
+  drop(client);
+  editor.join().unwrap();
+}
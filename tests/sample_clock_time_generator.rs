@@ -0,0 +1,88 @@
+use awoo::scheduler::{RandomAccessScheduler, StepResult};
+use awoo::time::TimeGenerator;
+use awoo::time::sample_clock::{SampleClockTimeGenerator, SampleCounter, Samples};
+use awoo::window::Window;
+use std::cell::RefCell;
+use std::thread;
+
+#[test]
+fn follows_counter() {
+  let counter = SampleCounter::new();
+  let mut gen = SampleClockTimeGenerator::new(counter.clone(), 48_000);
+
+  assert_eq!(gen.current(), 0.);
+
+  counter.advance(24_000);
+  assert_eq!(gen.tick(), 0.5);
+  assert_eq!(gen.samples(), 24_000);
+
+  // ticking doesn’t move time on its own
+  gen.tick();
+  gen.untick();
+  gen.change_delta(10.);
+  assert_eq!(gen.current(), 0.5);
+
+  counter.set(96_000);
+  assert_eq!(gen.current(), 2.);
+}
+
+#[test]
+fn raw_samples() {
+  let counter = SampleCounter::new();
+  let gen = SampleClockTimeGenerator::<Samples>::with_unit(counter.clone(), 44_100);
+
+  counter.advance(512);
+  counter.advance(512);
+  assert_eq!(gen.current(), 1024);
+  assert_eq!(gen.seconds(), 1024. / 44_100.);
+}
+
+#[test]
+fn set_and_reset() {
+  let counter = SampleCounter::new();
+  let mut gen = SampleClockTimeGenerator::new(counter.clone(), 1000);
+
+  counter.advance(1000);
+  gen.set(5.);
+  assert_eq!(gen.current(), 5.);
+
+  // the shift is kept as the counter moves; the counter itself is untouched
+  counter.advance(500);
+  assert_eq!(gen.current(), 5.5);
+  assert_eq!(counter.get(), 1500);
+
+  // time doesn’t go below zero
+  gen.set(-10.);
+  assert_eq!(gen.current(), 0.);
+
+  gen.reset();
+  assert_eq!(gen.current(), 1.5);
+}
+
+#[test]
+fn driven_by_audio_thread() {
+  let counter = SampleCounter::new();
+  let audio_counter = counter.clone();
+
+  let audio = thread::spawn(move || {
+    for _ in 0 .. 100 {
+      audio_counter.advance(480);
+      thread::yield_now();
+    }
+  });
+
+  let hits = RefCell::new(Vec::new());
+  let windows = vec![Window::new(0., 0.5).map(|t| hits.borrow_mut().push(t))];
+  let gen = SampleClockTimeGenerator::new(counter, 48_000);
+  let mut scheduler = RandomAccessScheduler::new(gen, windows).unwrap();
+
+  while scheduler.step() != StepResult::Finished {}
+
+  audio.join().unwrap();
+  drop(scheduler);
+
+  let hits = hits.into_inner();
+  assert!(!hits.is_empty());
+  assert!(hits.windows(2).all(|w| w[0] <= w[1]));
+  assert!(hits.iter().all(|&t| (0. .. 0.5).contains(&t)));
+}
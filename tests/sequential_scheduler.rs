@@ -0,0 +1,94 @@
+use awoo::scheduler::{RandomAccessScheduler, SequentialScheduler};
+use awoo::time::simple::SimpleF32TimeGenerator;
+use awoo::window::Window;
+use std::cell::RefCell;
+
+#[test]
+fn overlapping() {
+  let a = Window::new(0., 2.).map(|_| ());
+  let b = Window::new(1., 3.).map(|_| ());
+
+  assert!(SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 1.), vec![a, b]).is_err());
+}
+
+#[test]
+fn same_as_random_access() {
+  let seq_trace = RefCell::new(Vec::new());
+  let ra_trace = RefCell::new(Vec::new());
+
+  {
+    let windows = vec![
+      Window::new(2., 4.).map(|t| seq_trace.borrow_mut().push(('b', t))),
+      Window::new(0., 1.).map(|t| seq_trace.borrow_mut().push(('a', t))),
+      Window::new(4., 6.).map(|t| seq_trace.borrow_mut().push(('c', t))),
+    ];
+    let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 0.5), windows).unwrap();
+    scheduler.schedule();
+  }
+
+  {
+    let windows = vec![
+      Window::new(2., 4.).map(|t| ra_trace.borrow_mut().push(('b', t))),
+      Window::new(0., 1.).map(|t| ra_trace.borrow_mut().push(('a', t))),
+      Window::new(4., 6.).map(|t| ra_trace.borrow_mut().push(('c', t))),
+    ];
+    let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 0.5), windows).unwrap();
+    scheduler.schedule();
+  }
+
+  let seq_trace = seq_trace.into_inner();
+  assert_eq!(seq_trace, ra_trace.into_inner());
+  assert_eq!(seq_trace.first(), Some(&('a', 0.)));
+  assert_eq!(seq_trace.last(), Some(&('c', 5.5)));
+  assert!(!seq_trace.iter().any(|&(_, t)| (1. ..2.).contains(&t)));
+}
+
+#[test]
+fn seek_backwards_and_forward() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 1.).map(|t| trace.borrow_mut().push(('a', t))),
+    Window::new(1., 2.).map(|t| trace.borrow_mut().push(('b', t))),
+    Window::new(3., 4.).map(|t| trace.borrow_mut().push(('c', t))),
+  ];
+  let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert!(scheduler.seek(3.5));
+  assert!(scheduler.seek(0.5));
+  assert!(!scheduler.seek(2.5));
+  assert!(scheduler.seek(1.));
+  assert_eq!(*trace.borrow(), vec![('c', 3.5), ('a', 0.5), ('b', 1.)]);
+}
+
+#[test]
+fn nan_time() {
+  let windows = vec![Window::new(0., 1.).map(|_| ()), Window::new(1., 2.).map(|_| ())];
+  let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  assert!(!scheduler.evaluate_at(f32::NAN));
+  assert!(scheduler.evaluate_at(0.5));
+}
+
+#[test]
+fn replace_windows() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 4.).map(|t| trace.borrow_mut().push(('a', t))).on_leave(|t| trace.borrow_mut().push(('A', t))),
+  ];
+
+  let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+  scheduler.step();
+  scheduler.step();
+
+  // invalid windows are rejected and the scheduler is left as-is
+  let invalid = vec![Window::new(0., 2.).map(|_| ()), Window::new(1., 3.).map(|_| ())];
+  assert!(scheduler.replace_windows(invalid).is_err());
+  scheduler.step();
+
+  let windows = vec![Window::new(0., 4.).map(|t| trace.borrow_mut().push(('b', t)))];
+  scheduler.replace_windows(windows).unwrap();
+  scheduler.step();
+  drop(scheduler);
+
+  assert_eq!(trace.into_inner(), vec![('a', 0.), ('a', 1.), ('a', 2.), ('A', 3.), ('b', 3.)]);
+}
@@ -6,12 +6,10 @@ use awoo::time::simple::SimpleF32TimeGenerator;
 fn f32eq(a: f32, b: f32) -> bool {
   if a == b {
     true
+  } else if b == 0. {
+    a == 0.
   } else {
-    if b == 0. {
-      a == 0.
-    } else {
-      (a - b).abs() / b <= 2. * std::f32::EPSILON
-    }
+    (a - b).abs() / b <= 2. * f32::EPSILON
   }
 }
 
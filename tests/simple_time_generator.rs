@@ -0,0 +1,60 @@
+use awoo::scheduler::SequentialScheduler;
+use awoo::time::TimeGenerator;
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::window::Window;
+use std::cell::RefCell;
+use std::time::Duration;
+
+#[test]
+fn f64_precision() {
+  let mut gen = SimpleTimeGenerator::<f64>::new(3600. * 4., 1. / 60.);
+
+  gen.tick();
+  assert!((gen.current() - (3600. * 4. + 1. / 60.)).abs() < 1e-9);
+}
+
+#[test]
+fn frame_counter() {
+  let mut gen = SimpleTimeGenerator::new(0u64, 1);
+
+  assert_eq!(gen.tick(), 0);
+  assert_eq!(gen.tick(), 1);
+  assert_eq!(gen.current(), 2);
+
+  gen.set(0);
+  assert_eq!(gen.untick(), 0);
+  assert_eq!(gen.current(), 0);
+
+  let mut gen = SimpleTimeGenerator::new(0i64, 1);
+  gen.untick();
+  assert_eq!(gen.current(), -1);
+}
+
+#[test]
+fn duration() {
+  let mut gen = SimpleTimeGenerator::new(Duration::from_secs(1), Duration::from_millis(500));
+
+  gen.tick();
+  gen.tick();
+  assert_eq!(gen.current(), Duration::from_secs(2));
+
+  gen.change_delta(Duration::from_secs(3));
+  gen.untick();
+  assert_eq!(gen.current(), Duration::ZERO);
+
+  gen.reset();
+  assert_eq!(gen.current(), Duration::from_secs(1));
+}
+
+#[test]
+fn schedule_frames() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0u64, 2).map(|f| trace.borrow_mut().push(('a', f))),
+    Window::new(2, 4).map_local(|lt| trace.borrow_mut().push(('b', lt.local))),
+  ];
+  let mut scheduler = SequentialScheduler::new(SimpleTimeGenerator::new(0, 1), windows).unwrap();
+
+  scheduler.schedule();
+  assert_eq!(*trace.borrow(), vec![('a', 0), ('a', 1), ('b', 0), ('b', 1)]);
+}
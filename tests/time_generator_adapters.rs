@@ -0,0 +1,165 @@
+use awoo::scheduler::SequentialScheduler;
+use awoo::time::TimeGenerator;
+use awoo::time::adapter::{Clamp, Loop, Offset, PingPong, Reverse, Scale, TimeGeneratorExt};
+use awoo::time::rational::Rational;
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::window::Window;
+use std::cell::RefCell;
+
+fn ticks<G>(gen: &mut G, n: usize) -> Vec<G::Time> where G: TimeGenerator {
+  (0 .. n).map(|_| { gen.tick(); gen.current() }).collect()
+}
+
+#[test]
+fn offset() {
+  let mut gen = Offset::new(SimpleTimeGenerator::new(0, 1), 12);
+
+  assert_eq!(gen.current(), 12);
+  assert_eq!(ticks(&mut gen, 3), vec![13, 14, 15]);
+
+  gen.set(20);
+  assert_eq!(gen.inner().current(), 8);
+  assert_eq!(gen.current(), 20);
+
+  gen.reset();
+  assert_eq!(gen.current(), 12);
+}
+
+#[test]
+fn scale() {
+  let mut gen = Scale::new(SimpleTimeGenerator::new(0., 0.5), 2.);
+
+  assert_eq!(ticks(&mut gen, 3), vec![1., 2., 3.]);
+
+  gen.set(10.);
+  assert_eq!(gen.inner().current(), 5.);
+
+  // deltas are given in scaled time
+  gen.change_delta(4.);
+  assert_eq!(ticks(&mut gen, 1), vec![14.]);
+
+  let mut gen = SimpleTimeGenerator::new(Rational::from(0), Rational::new(1, 3)).scale(1.5);
+  assert_eq!(ticks(&mut gen, 2), vec![Rational::new(1, 2), Rational::from(1)]);
+}
+
+#[test]
+fn reverse() {
+  let mut gen = Reverse::new(SimpleTimeGenerator::new(10, 1));
+
+  assert_eq!(ticks(&mut gen, 3), vec![9, 8, 7]);
+
+  gen.untick();
+  assert_eq!(gen.current(), 8);
+
+  // reversing twice gives the original direction
+  let mut gen = SimpleTimeGenerator::new(10, 1).reverse().reverse();
+  assert_eq!(ticks(&mut gen, 2), vec![11, 12]);
+}
+
+#[test]
+fn clamp() {
+  let mut gen = Clamp::new(SimpleTimeGenerator::new(0, 1), 1, 3);
+
+  assert_eq!(gen.current(), 1);
+  assert_eq!(ticks(&mut gen, 4), vec![2, 3, 3, 3]);
+
+  // held at the bound, so going back takes effect immediately
+  gen.untick();
+  assert_eq!(gen.current(), 2);
+
+  gen.set(10);
+  assert_eq!(gen.current(), 3);
+}
+
+#[test]
+fn looping() {
+  let mut gen = Loop::new(SimpleTimeGenerator::new(0, 1), 0, 3);
+
+  assert_eq!(ticks(&mut gen, 7), vec![1, 2, 0, 1, 2, 0, 1]);
+
+  gen.set(0);
+  gen.untick();
+  assert_eq!(gen.current(), 2);
+
+  // large jumps
+  gen.set(10);
+  assert_eq!(gen.current(), 1);
+
+  let mut gen = SimpleTimeGenerator::new(0., 0.75).looping(0., 1.);
+  assert_eq!(ticks(&mut gen, 3), vec![0.75, 0.5, 0.25]);
+
+  // wrapping doesn’t depend on the number of periods jumped over
+  let mut gen = SimpleTimeGenerator::new(0f64, 0.001).looping(0., 0.001);
+  gen.set(1e5);
+  let t = gen.current();
+  assert!((0. .. 0.001).contains(&t) && t.min(0.001 - t) < 1e-9, "{}", t);
+
+  gen.set(-1e12);
+  assert!((0. .. 0.001).contains(&gen.current()));
+
+  let mut gen = Loop::new(SimpleTimeGenerator::new(0u64, 1), 5, 8);
+  gen.set(u64::MAX);
+  assert_eq!(gen.current(), 5 + (u64::MAX - 5) % 3);
+  gen.set(0);
+  assert_eq!(gen.current(), 6);
+
+  let mut gen = SimpleTimeGenerator::new(Rational::from(0), Rational::new(1, 3))
+    .looping(Rational::from(1), Rational::from(2));
+  gen.set(Rational::new(-7, 3));
+  assert_eq!(gen.current(), Rational::new(5, 3));
+}
+
+#[test]
+fn ping_pong() {
+  let mut gen = PingPong::new(SimpleTimeGenerator::new(0, 1), 0, 3);
+
+  assert_eq!(ticks(&mut gen, 8), vec![1, 2, 3, 2, 1, 0, 1, 2]);
+
+  gen.untick();
+  gen.untick();
+  gen.untick();
+  assert_eq!(gen.current(), 1);
+
+  // setting out of the bounds clamps in both directions
+  let mut gen = SimpleTimeGenerator::new(0., 0.25).ping_pong(0., 1.);
+  gen.set(-0.25);
+  assert_eq!(gen.current(), 0.);
+  gen.tick();
+  assert_eq!(gen.current(), 0.25);
+
+  gen.set(1.25);
+  assert_eq!(gen.current(), 1.);
+  gen.tick();
+  assert_eq!(gen.current(), 0.75);
+}
+
+#[test]
+#[should_panic]
+fn empty_loop() {
+  SimpleTimeGenerator::new(0, 1).looping(3, 3);
+}
+
+#[test]
+fn fluent_composition() {
+  // play twice as fast, starting at 12s, looping over 12..20s
+  let mut gen = SimpleTimeGenerator::new(0., 1.).scale(2.).offset(12.).looping(12., 20.);
+
+  assert_eq!(gen.current(), 12.);
+  assert_eq!(ticks(&mut gen, 5), vec![14., 16., 18., 12., 14.]);
+}
+
+#[test]
+fn schedule_adapted_time() {
+  let hits = RefCell::new(Vec::new());
+  let window = Window::new(0, 2).map(|t| hits.borrow_mut().push(t));
+  let gen = SimpleTimeGenerator::new(0, 1).ping_pong(0, 3).clamp(0, 3);
+
+  let mut scheduler = SequentialScheduler::new(gen, vec![window]).unwrap();
+
+  for _ in 0 .. 7 {
+    scheduler.step();
+  }
+
+  drop(scheduler);
+  assert_eq!(hits.into_inner(), vec![0, 1, 1, 0]);
+}
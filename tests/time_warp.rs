@@ -0,0 +1,104 @@
+use awoo::easing::Easing;
+use awoo::time::TimeGenerator;
+use awoo::time::adapter::{Curve, EasingCurve, TimeGeneratorExt, Warp};
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::track::{Interpolation, Key, Track};
+
+fn slow_then_fast() -> Track<f64, f64> {
+  Track::new(vec![
+    Key::new(0., 0., Interpolation::Linear),
+    Key::new(2., 1., Interpolation::Linear),
+    Key::new(4., 3., Interpolation::Linear),
+  ])
+}
+
+#[test]
+fn track_curve() {
+  let curve = slow_then_fast();
+
+  assert_eq!(curve.map(1.), 0.5);
+  assert_eq!(curve.map(3.), 2.);
+  assert_eq!(curve.map(10.), 3.);
+
+  assert_eq!(curve.inverse(1.), Some(2.));
+  assert_eq!(curve.inverse(3.), Some(4.));
+  assert!((curve.inverse(2.).unwrap() - 3.).abs() < 1e-9);
+  assert!((curve.inverse(0.25).unwrap() - 0.5).abs() < 1e-9);
+  assert_eq!(curve.inverse(4.), None);
+  assert_eq!(curve.inverse(-1.), None);
+  assert_eq!(Track::<f64, f64>::new(vec![]).inverse(0.), None);
+
+  // only non-decreasing curves that don’t overshoot can be inverted
+  let decreasing = Track::new(vec![
+    Key::new(0., 0., Interpolation::Linear),
+    Key::new(1., 2., Interpolation::Linear),
+    Key::new(2., 1., Interpolation::Linear),
+  ]);
+  assert_eq!(decreasing.inverse(0.5), None);
+
+  let catmull_rom = Track::new(vec![Key::new(0., 0., Interpolation::CatmullRom), Key::new(1., 1., Interpolation::Linear)]);
+  assert_eq!(catmull_rom.inverse(0.5), None);
+
+  let bounce = Track::new(vec![Key::new(0., 0., Interpolation::Eased(Easing::BounceOut)), Key::new(1., 1., Interpolation::Linear)]);
+  assert_eq!(bounce.inverse(0.5), None);
+
+  let eased = Track::new(vec![Key::new(0., 0., Interpolation::Eased(Easing::QuadIn)), Key::new(1., 1., Interpolation::Linear)]);
+  assert!((eased.inverse(0.25).unwrap() - 0.5f32).abs() < 1e-6);
+
+  // values jumped over by steps can’t be reached
+  let step = Track::new(vec![Key::new(0., 0., Interpolation::Step), Key::new(1., 1., Interpolation::Linear)]);
+  assert_eq!(step.inverse(0.5), None);
+  assert_eq!(step.inverse(0.), Some(0.));
+  assert_eq!(step.inverse(1.), Some(1.));
+
+  let mut gen = Warp::new(SimpleTimeGenerator::new(0., 1.), step);
+  assert!(!gen.try_set(0.5));
+  assert_eq!(gen.current(), 0.);
+}
+
+#[test]
+fn easing_curve() {
+  let curve = EasingCurve::new(0f64, 4., Easing::QuadIn);
+
+  assert_eq!(curve.map(2.), 1.);
+  assert_eq!(curve.map(-1.), -1.);
+  assert_eq!(curve.map(5.), 5.);
+  assert!((curve.inverse(1.).unwrap() - 2.).abs() < 1e-6);
+  assert_eq!(curve.inverse(5.), Some(5.));
+
+  // overshooting easing functions can’t be inverted
+  let curve = EasingCurve::new(0f64, 4., Easing::ElasticOut);
+  assert_eq!(curve.inverse(1.), None);
+  assert_eq!(curve.inverse(6.), Some(6.));
+}
+
+#[test]
+fn warp() {
+  let mut gen = Warp::new(SimpleTimeGenerator::new(0., 1.), slow_then_fast());
+  let times: Vec<f64> = (0 .. 5).map(|_| { gen.tick(); gen.current() }).collect();
+
+  assert_eq!(times, vec![0.5, 1., 2., 3., 3.]);
+
+  gen.set(2.);
+  assert!((gen.inner().current() - 3.).abs() < 1e-9);
+
+  assert!(!gen.try_set(10.));
+  assert!((gen.inner().current() - 3.).abs() < 1e-9);
+
+  gen.untick();
+  assert_eq!(gen.current(), 1.);
+}
+
+#[test]
+fn warp_closure() {
+  let mut gen = SimpleTimeGenerator::new(0., 1.).warp(|t: f64| t * t);
+
+  gen.tick();
+  gen.tick();
+  gen.tick();
+  assert_eq!(gen.current(), 9.);
+
+  // closures can’t be inverted
+  assert!(!gen.try_set(4.));
+  assert_eq!(gen.current(), 9.);
+}
@@ -0,0 +1,115 @@
+use awoo::scheduler::SequentialScheduler;
+use awoo::time::timecode::{FrameRate, Timecode, TimecodeError, TimecodeTimeGenerator};
+use awoo::time::{Arithmetic, TimeGenerator};
+use awoo::window::Window;
+use std::cell::RefCell;
+
+#[test]
+fn non_drop_frame() {
+  let tc = Timecode::new(1, 0, 0, 0, FrameRate::Fps25).unwrap();
+
+  assert_eq!(tc.frames(), 90_000);
+  assert_eq!(tc.seconds(), 3600.);
+  assert_eq!(tc.to_string(), "01:00:00:00");
+  assert_eq!(Timecode::from_frames(86_399, FrameRate::Fps24).to_string(), "00:59:59:23");
+  assert_eq!(Timecode::new(0, 0, 0, 24, FrameRate::Fps24), Err(TimecodeError::OutOfRange));
+}
+
+#[test]
+fn drop_frame() {
+  let rate = FrameRate::Fps29_97DropFrame;
+
+  assert_eq!(Timecode::from_frames(1799, rate).to_string(), "00:00:59;29");
+  assert_eq!(Timecode::from_frames(1800, rate).to_string(), "00:01:00;02");
+  assert_eq!(Timecode::from_frames(17_982, rate).to_string(), "00:10:00;00");
+  assert_eq!(Timecode::from_frames(107_892, rate).to_string(), "01:00:00;00");
+
+  assert_eq!(Timecode::new(0, 1, 0, 2, rate).unwrap().frames(), 1800);
+  assert_eq!(Timecode::new(0, 10, 0, 0, rate).unwrap().frames(), 17_982);
+  assert_eq!(Timecode::new(0, 1, 0, 0, rate), Err(TimecodeError::DroppedFrame));
+
+  // labels and frames round-trip
+  for frame in 0 .. 40_000 {
+    let tc = Timecode::from_frames(frame, rate);
+    assert_eq!(Timecode::parse(&tc.to_string(), rate), Ok(tc));
+  }
+}
+
+#[test]
+fn parse() {
+  assert_eq!(Timecode::parse("00:00:01:05", FrameRate::Fps30).unwrap().frames(), 35);
+  assert_eq!("00:00:01:05@25".parse::<Timecode>().unwrap(), Timecode::from_frames(30, FrameRate::Fps25));
+  assert_eq!("00:01:00;02".parse::<Timecode>().unwrap().frames(), 1800);
+
+  assert!(matches!(Timecode::parse("00:00:01;05", FrameRate::Fps30), Err(TimecodeError::Malformed(_))));
+  assert!(matches!(Timecode::parse("00:01:05", FrameRate::Fps30), Err(TimecodeError::Malformed(_))));
+  assert!(matches!(Timecode::parse("00:00:+1:05", FrameRate::Fps30), Err(TimecodeError::Malformed(_))));
+  assert!(matches!("00:00:01:05".parse::<Timecode>(), Err(TimecodeError::MissingFrameRate(_))));
+  assert!(matches!("00:00:01:05@60".parse::<Timecode>(), Err(TimecodeError::UnknownFrameRate(_))));
+
+  // hours too large to be represented as frames
+  assert_eq!("300000000000000:00:00:00@25".parse::<Timecode>(), Err(TimecodeError::OutOfRange));
+  assert_eq!(Timecode::new(u64::MAX, 0, 0, 0, FrameRate::Fps24), Err(TimecodeError::OutOfRange));
+}
+
+#[test]
+fn ordering_and_arithmetic() {
+  let a = Timecode::from_frames(24, FrameRate::Fps24);
+  let b = Timecode::from_frames(25, FrameRate::Fps25);
+  let c = Timecode::from_frames(26, FrameRate::Fps25);
+
+  assert_eq!(a, b);
+  assert!(a < c);
+
+  // the left operand’s frame rate is kept
+  assert_eq!((a + c).frames(), 49);
+  assert_eq!((a + c).rate(), FrameRate::Fps24);
+  assert_eq!((a - c).frames(), 0);
+
+  // zero adopts the frame rate of the other operand
+  assert_eq!(Timecode::zero().add(c).rate(), FrameRate::Fps25);
+  assert_eq!(c.times(3).frames(), 78);
+}
+
+#[test]
+fn frame_by_frame() {
+  let start = Timecode::parse("00:00:59;28", FrameRate::Fps29_97DropFrame).unwrap();
+  let mut gen = TimecodeTimeGenerator::frame_by_frame(start);
+
+  gen.tick();
+  gen.tick();
+  assert_eq!(gen.current().to_string(), "00:01:00;02");
+
+  gen.untick();
+  assert_eq!(gen.current().to_string(), "00:00:59;29");
+
+  gen.reset();
+  assert_eq!(gen.current(), start);
+}
+
+#[test]
+fn schedule_timecodes() {
+  let rate = FrameRate::Fps25;
+  let frames = RefCell::new(Vec::new());
+  let window = Window::new(Timecode::parse("00:00:00:02", rate).unwrap(), Timecode::parse("00:00:00:04", rate).unwrap())
+    .map(|t: Timecode| frames.borrow_mut().push(t.frames()));
+
+  let gen = TimecodeTimeGenerator::frame_by_frame(Timecode::from_frames(0, rate));
+  let mut scheduler = SequentialScheduler::new(gen, vec![window]).unwrap();
+  scheduler.schedule();
+  drop(scheduler);
+
+  assert_eq!(frames.into_inner(), vec![2, 3]);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn serialize() {
+  let tc = Timecode::parse("01:00:00;00", FrameRate::Fps29_97DropFrame).unwrap();
+  let json = serde_json::to_string(&tc).unwrap();
+
+  assert_eq!(json, "\"01:00:00;00@29.97\"");
+  assert_eq!(serde_json::from_str::<Timecode>(&json).unwrap(), tc);
+  assert!(serde_json::from_str::<Timecode>("\"01:00:00:00\"").is_err());
+  assert!(serde_json::from_str::<Timecode>("\"300000000000000:00:00:00@25\"").is_err());
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "json")]
+
+use awoo::easing::Easing;
+use awoo::scheduler::SchedulerError;
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::timeline::{ActionRegistry, Timeline, TimelineError};
+use awoo::window::Window;
+use std::cell::RefCell;
+
+const TIMELINE: &str = r#"
+{
+  "windows": [
+    { "name": "flash", "start": 0, "end": 2 },
+    { "name": "scene", "start": 2, "end": 5, "easing": "QuadInOut", "metadata": { "camera": "orbit" } },
+    { "name": "flash", "start": 5, "end": 6, "layer": 1 }
+  ]
+}"#;
+
+#[test]
+fn deserialize() {
+  let timeline: Timeline<u32> = serde_json::from_str(TIMELINE).unwrap();
+
+  assert_eq!(timeline.windows.len(), 3);
+  assert_eq!(timeline.windows[1].window(), Window::new(2, 5));
+  assert_eq!(timeline.windows[1].metadata["camera"], "orbit");
+  assert_eq!(timeline.windows[1].easing, Some(Easing::QuadInOut));
+  assert_eq!(timeline.windows[2].easing, None);
+  assert_eq!(timeline.windows[2].layer, 1);
+}
+
+#[test]
+fn serialize() {
+  let mut timeline = Timeline::new();
+  timeline.push("flash", Window::new(0, 2));
+  timeline
+    .push("scene", Window::new(2, 5))
+    .with_easing(Easing::QuadInOut)
+    .with_metadata("camera", "orbit");
+  timeline.push("flash", Window::new(5, 6)).with_layer(1);
+
+  let json = serde_json::to_string(&timeline).unwrap();
+  assert!(json.contains(r#""easing":"QuadInOut""#));
+  assert_eq!(json.matches("easing").count(), 1);
+
+  assert_eq!(serde_json::from_str::<Timeline<u32>>(&json).unwrap(), timeline);
+  assert_eq!(timeline, serde_json::from_str(TIMELINE).unwrap());
+}
+
+#[test]
+fn build_scheduler() {
+  let timeline: Timeline<u32> = serde_json::from_str(TIMELINE).unwrap();
+  let hits = RefCell::new(Vec::new());
+
+  let mut registry = ActionRegistry::new();
+  registry
+    .register("flash", |t| hits.borrow_mut().push(("flash", t)))
+    .register("scene", |t| hits.borrow_mut().push(("scene", t)));
+
+  let mut scheduler = registry.sequential_scheduler(SimpleTimeGenerator::new(0, 1), &timeline).unwrap();
+  scheduler.schedule();
+  drop(scheduler);
+  drop(registry);
+
+  assert_eq!(
+    hits.into_inner(),
+    vec![("flash", 0), ("flash", 1), ("scene", 2), ("scene", 3), ("scene", 4), ("flash", 5)]
+  );
+}
+
+#[test]
+fn local_actions() {
+  let mut timeline = Timeline::new();
+  timeline.push("fade", Window::new(0., 4.)).with_easing(Easing::QuadIn);
+  timeline.push("fade", Window::new(4., 6.));
+  let progress = RefCell::new(Vec::new());
+
+  let mut registry = ActionRegistry::new();
+  registry.register_local("fade", |local| progress.borrow_mut().push((local.local, local.progress)));
+
+  let mut scheduler = registry.sequential_scheduler(SimpleTimeGenerator::new(0f32, 1.), &timeline).unwrap();
+  scheduler.schedule();
+  drop(scheduler);
+  drop(registry);
+
+  assert_eq!(
+    progress.into_inner(),
+    vec![(0., 0.), (1., 0.0625), (2., 0.25), (3., 0.5625), (0., 0.), (1., 0.5)]
+  );
+}
+
+#[test]
+fn binding_errors() {
+  let timeline: Timeline<u32> = serde_json::from_str(TIMELINE).unwrap();
+
+  let mut registry = ActionRegistry::new();
+  registry
+    .register("scene", |_| ())
+    .register("outro", |_| ())
+    .register("credits", |_| ());
+
+  let err = registry.random_access_scheduler(SimpleTimeGenerator::new(0, 1), &timeline).err().unwrap();
+
+  assert_eq!(
+    err,
+    TimelineError::Binding {
+      unbound: vec!["flash".to_owned()],
+      unused: vec!["credits".to_owned(), "outro".to_owned()]
+    }
+  );
+  assert_eq!(err.to_string(), "invalid timeline bindings; unbound actions: flash; unused actions: credits, outro");
+}
+
+#[test]
+fn scheduler_errors() {
+  let timeline: Timeline<u32> = serde_json::from_str(TIMELINE).unwrap();
+
+  let mut registry = ActionRegistry::new();
+  registry.register("flash", |_| ()).register("scene", |_| ());
+
+  // fine with the layered scheduler, which accepts overlapping windows
+  assert!(registry.layered_scheduler(SimpleTimeGenerator::new(0, 1), &timeline).is_ok());
+
+  let mut timeline = timeline;
+  timeline.windows[2].start = 4;
+
+  let err = registry.random_access_scheduler(SimpleTimeGenerator::new(0, 1), &timeline).err().unwrap();
+
+  assert_eq!(
+    err,
+    TimelineError::Scheduler(SchedulerError::Overlapping {
+      first: 1,
+      first_window: Window::new(2, 5),
+      second: 2,
+      second_window: Window::new(4, 6)
+    })
+  );
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "serde")]
+
+use awoo::timeline::{Format, Timeline, TimelineFileError};
+use awoo::window::Window;
+use std::fs;
+use std::path::PathBuf;
+
+fn timeline() -> Timeline<u32> {
+  let mut timeline = Timeline::new();
+  timeline.push("flash", Window::new(0, 2));
+  timeline.push("scene", Window::new(2, 5)).with_layer(1).with_metadata("camera", "orbit");
+  timeline
+}
+
+fn temp_path(name: &str, ext: &str) -> PathBuf {
+  std::env::temp_dir().join(format!("awoo-format-{}-{}.{}", name, std::process::id(), ext))
+}
+
+#[test]
+fn format_from_path() {
+  assert_eq!(Format::from_path("demo/timeline.json"), Some(Format::Json));
+  assert_eq!(Format::from_path("timeline.ron"), Some(Format::Ron));
+  assert_eq!(Format::from_path("TIMELINE.TOML"), Some(Format::Toml));
+  assert_eq!(Format::from_path("timeline.yaml"), None);
+  assert_eq!(Format::from_path("timeline"), None);
+}
+
+#[test]
+fn unknown_format() {
+  let path = temp_path("unknown", "yaml");
+
+  assert!(matches!(timeline().save(&path), Err(TimelineFileError::UnknownFormat(_))));
+  assert!(matches!(Timeline::<u32>::load(&path), Err(TimelineFileError::UnknownFormat(_))));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_round_trip() {
+  let path = temp_path("json", "json");
+  timeline().save(&path).unwrap();
+
+  let loaded: Timeline<u32> = Timeline::load(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+
+  assert_eq!(loaded.windows, timeline().windows);
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn ron_round_trip() {
+  let path = temp_path("ron", "ron");
+  timeline().save(&path).unwrap();
+
+  let loaded: Timeline<u32> = Timeline::load(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+
+  assert_eq!(loaded.windows, timeline().windows);
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn ron_parse() {
+  let timeline: Timeline<u32> = Timeline::parse(
+    r#"
+    (
+      windows: [
+        // the intro
+        (name: "flash", start: 0, end: 2),
+        (name: "scene", start: 2, end: 5, layer: 1, metadata: { "camera": "orbit" }),
+      ],
+    )
+    "#,
+    Format::Ron
+  ).unwrap();
+
+  assert_eq!(timeline.windows, self::timeline().windows);
+
+  let err = Timeline::<u32>::parse("(windows: [(name: \"flash\")])", Format::Ron).unwrap_err();
+  assert!(matches!(err, TimelineFileError::Deserialize { format: Format::Ron, .. }));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_round_trip() {
+  let path = temp_path("toml", "toml");
+  timeline().save(&path).unwrap();
+
+  let loaded: Timeline<u32> = Timeline::load(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+
+  assert_eq!(loaded.windows, timeline().windows);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_parse() {
+  let timeline: Timeline<u32> = Timeline::parse(
+    r#"
+    # the intro
+    [[windows]]
+    name = "flash"
+    start = 0
+    end = 2
+
+    [[windows]]
+    name = "scene"
+    start = 2
+    end = 5
+    layer = 1
+    metadata = { camera = "orbit" }
+    "#,
+    Format::Toml
+  ).unwrap();
+
+  assert_eq!(timeline.windows, self::timeline().windows);
+
+  let err = Timeline::<u32>::parse("[[windows]]\nname = \"flash\"", Format::Toml).unwrap_err();
+  assert!(matches!(err, TimelineFileError::Deserialize { format: Format::Toml, .. }));
+}
+
+#[cfg(not(feature = "toml"))]
+#[test]
+fn unsupported_format() {
+  let err = timeline().to_string_as(Format::Toml).unwrap_err();
+  assert!(matches!(err, TimelineFileError::UnsupportedFormat(Format::Toml)));
+}
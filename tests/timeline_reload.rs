@@ -0,0 +1,103 @@
+#![cfg(feature = "json")]
+
+use awoo::time::TimeGenerator;
+use awoo::time::simple::SimpleTimeGenerator;
+use awoo::timeline::reload::{ReloadError, TimelineWatcher};
+use awoo::timeline::{ActionRegistry, Format, Timeline, TimelineError, TimelineFileError};
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+// write a file and bump its modification time, so that changes are seen even on file systems with a
+// coarse time resolution
+fn write(path: &PathBuf, content: &str, age: u64) {
+  fs::write(path, content).unwrap();
+
+  let mtime = SystemTime::now() - Duration::from_secs(100 - age);
+  File::options().write(true).open(path).unwrap().set_modified(mtime).unwrap();
+}
+
+fn temp_path(name: &str) -> PathBuf {
+  std::env::temp_dir().join(format!("awoo-{}-{}.json", name, std::process::id()))
+}
+
+#[test]
+fn load_json() {
+  let path = temp_path("load");
+  write(&path, r#"{ "windows": [{ "name": "a", "start": 0, "end": 1 }] }"#, 0);
+
+  let timeline: Timeline<u32> = Timeline::load_json(&path).unwrap();
+  assert_eq!(timeline.windows[0].name, "a");
+
+  fs::remove_file(&path).unwrap();
+  assert!(matches!(Timeline::<u32>::load_json(&path), Err(TimelineFileError::Io(_))));
+  assert!(matches!(Timeline::<u32>::from_json("{"), Err(TimelineFileError::Deserialize { format: Format::Json, .. })));
+}
+
+#[test]
+fn hot_reload() {
+  let path = temp_path("reload");
+  write(&path, r#"{ "windows": [{ "name": "a", "start": 0, "end": 10 }] }"#, 0);
+
+  let hits = RefCell::new(Vec::new());
+  let mut registry = ActionRegistry::new();
+  registry
+    .register("a", |t| hits.borrow_mut().push(("a", t)))
+    .register("b", |t| hits.borrow_mut().push(("b", t)));
+
+  // the registry requires all actions to be used
+  let timeline: Timeline<u32> = Timeline::load_json(&path).unwrap();
+  assert!(registry.random_access_scheduler(SimpleTimeGenerator::new(0, 1), &timeline).is_err());
+
+  write(&path, r#"{ "windows": [{ "name": "a", "start": 0, "end": 4 }, { "name": "b", "start": 4, "end": 10 }] }"#, 1);
+
+  let timeline: Timeline<u32> = Timeline::load_json(&path).unwrap();
+  let mut scheduler = registry.random_access_scheduler(SimpleTimeGenerator::new(0, 1), &timeline).unwrap();
+  let mut watcher = TimelineWatcher::new(&path);
+
+  // nothing changed yet
+  assert!(watcher.reload(&registry, &mut scheduler).is_none());
+  scheduler.step();
+  scheduler.step();
+
+  // move the boundary
+  write(&path, r#"{ "windows": [{ "name": "a", "start": 0, "end": 3 }, { "name": "b", "start": 3, "end": 10 }] }"#, 2);
+  assert!(matches!(watcher.reload(&registry, &mut scheduler), Some(Ok(()))));
+  assert!(watcher.reload(&registry, &mut scheduler).is_none());
+  assert_eq!(scheduler.time_gen().current(), 2);
+  scheduler.step();
+  scheduler.step();
+
+  // invalid files are reported, and playback goes on with the previous windows
+  write(&path, r#"{ "windows": [{ "name": "a", "start": 0, "end": 3 "#, 3);
+  assert!(matches!(watcher.reload(&registry, &mut scheduler), Some(Err(ReloadError::File(TimelineFileError::Deserialize { format: Format::Json, .. })))));
+  scheduler.step();
+
+  write(&path, r#"{ "windows": [{ "name": "a", "start": 0, "end": 5 }, { "name": "b", "start": 4, "end": 10 }] }"#, 4);
+  assert!(matches!(
+    watcher.reload(&registry, &mut scheduler),
+    Some(Err(ReloadError::Timeline(TimelineError::Scheduler(_))))
+  ));
+  scheduler.step();
+
+  write(&path, r#"{ "windows": [{ "name": "c", "start": 0, "end": 5 }] }"#, 5);
+  assert!(matches!(
+    watcher.reload(&registry, &mut scheduler),
+    Some(Err(ReloadError::Timeline(TimelineError::Binding { .. })))
+  ));
+  scheduler.step();
+
+  // missing files are reported once
+  fs::remove_file(&path).unwrap();
+  assert!(matches!(watcher.reload(&registry, &mut scheduler), Some(Err(ReloadError::File(TimelineFileError::Io(_))))));
+  assert!(watcher.reload(&registry, &mut scheduler).is_none());
+
+  drop(scheduler);
+  drop(registry);
+
+  assert_eq!(
+    hits.into_inner(),
+    vec![("a", 0), ("a", 1), ("a", 2), ("b", 3), ("b", 4), ("b", 5), ("b", 6)]
+  );
+}
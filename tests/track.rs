@@ -0,0 +1,71 @@
+use awoo::track::{Interpolation, Key, Track};
+
+#[test]
+fn empty() {
+  let track: Track<f32, f32> = Track::new(Vec::new());
+  assert_eq!(track.sample(0.), None);
+}
+
+#[test]
+fn clamped() {
+  let track = Track::new(vec![Key::new(1., 10., Interpolation::Linear), Key::new(2., 20., Interpolation::Linear)]);
+
+  assert_eq!(track.sample(0.), Some(10.));
+  assert_eq!(track.sample(2.), Some(20.));
+  assert_eq!(track.sample(3.), Some(20.));
+}
+
+#[test]
+fn interpolations() {
+  let track: Track<f32, f32> = Track::new(vec![
+    Key::new(2., 20., Interpolation::Cosine),
+    Key::new(0., 0., Interpolation::Step),
+    Key::new(1., 10., Interpolation::Linear),
+    Key::new(3., 0., Interpolation::Linear),
+  ]);
+
+  assert_eq!(track.sample(0.5), Some(0.));
+  assert_eq!(track.sample(1.), Some(10.));
+  assert_eq!(track.sample(1.25), Some(12.5));
+  assert_eq!(track.sample(2.), Some(20.));
+  assert!((track.sample(2.5).unwrap() - 10.).abs() < 1e-5);
+  assert!(track.sample(2.25).unwrap() > 15.);
+}
+
+#[test]
+fn catmull_rom() {
+  // keys on a line give a line
+  let track = Track::new(vec![
+    Key::new(0., 0., Interpolation::CatmullRom),
+    Key::new(1., 1., Interpolation::CatmullRom),
+    Key::new(2., 2., Interpolation::CatmullRom),
+    Key::new(3., 3., Interpolation::CatmullRom),
+  ]);
+
+  for i in 0..30 {
+    let t = 1. + i as f64 / 30.;
+    assert!((track.sample(t).unwrap() - t).abs() < 1e-6);
+  }
+
+  // passes through keys
+  let track = Track::new(vec![
+    Key::new(0., [0., 1.], Interpolation::CatmullRom),
+    Key::new(1., [4., -1.], Interpolation::CatmullRom),
+    Key::new(2., [2., 0.], Interpolation::CatmullRom),
+  ]);
+
+  assert_eq!(track.sample(1.), Some([4., -1.]));
+  assert_eq!(track.sample(0.), Some([0., 1.]));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn deserialize_sorts_keys() {
+  let track: Track<f32, f32> = serde_json::from_str(r#"[
+    { "t": 1, "value": 3, "interpolation": "Step" },
+    { "t": 0, "value": 2, "interpolation": "Linear" }
+  ]"#).unwrap();
+
+  assert_eq!(track.keys()[0].t, 0.);
+  assert_eq!(track.sample(0.5), Some(2.5));
+}
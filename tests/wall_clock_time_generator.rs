@@ -0,0 +1,73 @@
+use awoo::scheduler::RandomAccessScheduler;
+use awoo::time::TimeGenerator;
+use awoo::time::wall_clock::{ManualClock, WallClockTimeGenerator};
+use awoo::window::Window;
+use std::cell::RefCell;
+use std::time::Duration;
+
+#[test]
+fn follows_clock() {
+  let clock = ManualClock::new();
+  let mut gen = WallClockTimeGenerator::with_clock(&clock, 1.);
+
+  clock.advance(Duration::from_millis(250));
+  assert_eq!(gen.tick(), 1.);
+  assert_eq!(gen.current(), 1.25);
+
+  // no time elapsed
+  gen.tick();
+  assert_eq!(gen.current(), 1.25);
+
+  clock.advance(Duration::from_millis(500));
+  gen.untick();
+  assert_eq!(gen.current(), 0.75);
+
+  gen.reset();
+  clock.advance(Duration::from_secs(1));
+  gen.tick();
+  assert_eq!(gen.current(), 2.);
+}
+
+#[test]
+fn pause_and_speed() {
+  let clock = ManualClock::new();
+  let mut gen = WallClockTimeGenerator::with_clock(&clock, 0.);
+
+  gen.pause();
+  clock.advance(Duration::from_secs(1));
+  gen.tick();
+  clock.advance(Duration::from_secs(1));
+  gen.resume();
+  gen.tick();
+  assert_eq!(gen.current(), 0.);
+
+  gen.change_delta(2.);
+  clock.advance(Duration::from_secs(1));
+  gen.tick();
+  assert_eq!(gen.current(), 2.);
+
+  gen.set_speed(-0.5);
+  clock.advance(Duration::from_secs(1));
+  gen.tick();
+  assert_eq!(gen.current(), 1.5);
+}
+
+#[test]
+fn scheduled() {
+  let clock = ManualClock::new();
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![Window::new(0., 1.).map(|t| trace.borrow_mut().push(t))];
+  let mut scheduler = RandomAccessScheduler::new(WallClockTimeGenerator::with_clock(&clock, 0.), windows).unwrap();
+
+  // actions run at the current time, and then time is ticked
+  scheduler.step();
+  clock.advance(Duration::from_millis(500));
+  scheduler.step();
+  scheduler.time_gen_mut().pause();
+  clock.advance(Duration::from_millis(500));
+  scheduler.step();
+  scheduler.step();
+
+  assert_eq!(*trace.borrow(), vec![0., 0., 0.5, 0.5]);
+  assert!(scheduler.time_gen().is_paused());
+}
@@ -0,0 +1,23 @@
+use awoo::scheduler::RandomAccessScheduler;
+use awoo::time::simple::SimpleF32TimeGenerator;
+use awoo::window::{LocalTime, Window};
+use std::cell::RefCell;
+
+#[test]
+fn map_local() {
+  let trace = RefCell::new(Vec::new());
+  let windows = vec![
+    Window::new(0., 2.).map(|_| ()),
+    Window::new(2., 6.).map_local(|lt| trace.borrow_mut().push(lt)),
+  ];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  scheduler.schedule();
+
+  assert_eq!(*trace.borrow(), vec![
+    LocalTime { time: 2., local: 0., progress: 0. },
+    LocalTime { time: 3., local: 1., progress: 0.25 },
+    LocalTime { time: 4., local: 2., progress: 0.5 },
+    LocalTime { time: 5., local: 3., progress: 0.75 },
+  ]);
+}
@@ -0,0 +1,122 @@
+use awoo::scheduler::layered::LayeredScheduler;
+use awoo::scheduler::{RandomAccessScheduler, SequentialScheduler, SkipPolicy};
+use awoo::time::simple::SimpleF32TimeGenerator;
+use awoo::window::{MappedWindow, Window};
+use std::cell::RefCell;
+
+type Trace = RefCell<Vec<String>>;
+
+fn traced<'a>(trace: &'a Trace, name: &'static str, start: f32, end: f32) -> MappedWindow<'a, f32> {
+  Window::new(start, end)
+    .map(move |_| ())
+    .on_enter(move |t| trace.borrow_mut().push(format!("enter {} {}", name, t)))
+    .on_leave(move |t| trace.borrow_mut().push(format!("leave {} {}", name, t)))
+}
+
+#[test]
+fn enter_leave_once() {
+  let trace = Trace::default();
+  let windows = vec![traced(&trace, "a", 0., 2.), traced(&trace, "b", 3., 5.)];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 0.5), windows).unwrap();
+
+  scheduler.schedule();
+
+  assert_eq!(*trace.borrow(), vec!["enter a 0", "leave a 2", "enter b 3", "leave b 5"]);
+}
+
+#[test]
+fn jumps() {
+  let trace = Trace::default();
+  let windows = vec![traced(&trace, "a", 0., 1.), traced(&trace, "b", 1., 2.), traced(&trace, "c", 2., 3.)];
+  let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  scheduler.seek(0.5);
+  scheduler.seek(2.5);
+  scheduler.seek(0.);
+  assert_eq!(*trace.borrow(), vec!["enter a 0.5", "leave a 2.5", "enter c 2.5", "leave c 0", "enter a 0"]);
+
+  trace.borrow_mut().clear();
+  scheduler.set_skip_policy(SkipPolicy::Transit);
+  scheduler.seek(2.5);
+  scheduler.seek(0.);
+  assert_eq!(*trace.borrow(), vec![
+    "leave a 2.5", "enter b 2.5", "leave b 2.5", "enter c 2.5",
+    "leave c 0", "enter b 0", "leave b 0", "enter a 0",
+  ]);
+
+  trace.borrow_mut().clear();
+  scheduler.reset();
+  assert_eq!(*trace.borrow(), vec!["leave a 0"]);
+}
+
+#[test]
+fn gap_to_gap() {
+  let trace = Trace::default();
+  let windows = vec![traced(&trace, "a", 1., 2.), traced(&trace, "b", 5., 6.)];
+  let mut scheduler = RandomAccessScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+  scheduler.set_skip_policy(SkipPolicy::Transit);
+
+  scheduler.seek(0.5);
+  scheduler.seek(3.);
+  assert_eq!(*trace.borrow(), vec!["enter a 3", "leave a 3"]);
+
+  trace.borrow_mut().clear();
+  scheduler.seek(7.);
+  scheduler.seek(0.);
+  assert_eq!(*trace.borrow(), vec!["enter b 7", "leave b 7", "enter b 0", "leave b 0", "enter a 0", "leave a 0"]);
+}
+
+#[test]
+fn tick_larger_than_window() {
+  let trace = Trace::default();
+  let windows = vec![traced(&trace, "a", 1., 2.), traced(&trace, "b", 5., 8.)];
+  let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 3.), windows).unwrap();
+  scheduler.set_skip_policy(SkipPolicy::Transit);
+
+  // time goes through 0, 3, 6 and 9, jumping over a from a gap to another one
+  scheduler.schedule();
+
+  assert_eq!(*trace.borrow(), vec!["enter a 3", "leave a 3", "enter b 6", "leave b 9"]);
+}
+
+#[test]
+fn last_window_skipped() {
+  let trace = Trace::default();
+  let windows = vec![traced(&trace, "a", 1., 2.)];
+  let mut scheduler = SequentialScheduler::new(SimpleF32TimeGenerator::new(0., 3.), windows).unwrap();
+  scheduler.set_skip_policy(SkipPolicy::Transit);
+
+  // the tick finishing the timeline jumps over a
+  scheduler.schedule();
+  assert_eq!(*trace.borrow(), vec!["enter a 3", "leave a 3"]);
+
+  trace.borrow_mut().clear();
+  scheduler.set_skip_policy(SkipPolicy::Ignore);
+  scheduler.reset();
+  scheduler.schedule();
+  assert!(trace.borrow().is_empty());
+}
+
+#[test]
+fn layered() {
+  let trace = Trace::default();
+  let windows = vec![
+    traced(&trace, "camera", 0., 10.),
+    traced(&trace, "a", 1., 2.),
+    traced(&trace, "b", 3., 4.),
+  ];
+  let mut scheduler = LayeredScheduler::new(SimpleF32TimeGenerator::new(0., 1.), windows).unwrap();
+
+  scheduler.seek(1.);
+  scheduler.seek(5.);
+  assert_eq!(*trace.borrow(), vec!["enter camera 1", "enter a 1", "leave a 5"]);
+
+  trace.borrow_mut().clear();
+  scheduler.set_skip_policy(SkipPolicy::Transit);
+  scheduler.seek(0.);
+  assert_eq!(*trace.borrow(), vec!["enter b 0", "leave b 0", "enter a 0", "leave a 0"]);
+
+  trace.borrow_mut().clear();
+  scheduler.step_at(9.);
+  assert_eq!(*trace.borrow(), vec!["enter a 9", "leave a 9", "enter b 9", "leave b 9", "leave camera 10"]);
+}